@@ -0,0 +1,189 @@
+//  Copyright (c) Klas Henriksson 2022.
+//  All rights reserved.
+
+use std::fmt;
+
+use crate::gen::{get_neighbor, get_neighbors, get_open_neighbors, Dir, Maze};
+use crate::solver::DistanceMap;
+
+/// Statistics describing the structure of a finished maze, used to compare the output of generators.
+/// Cells are classified by their number of open passages, not counting openings in the outer boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeStats {
+    pub cell_count: usize,
+    /// Cells with a single open passage.
+    pub dead_ends: usize,
+    /// Cells with two open passages.
+    pub corridors: usize,
+    /// Cells with three open passages.
+    pub junctions: usize,
+    /// Cells with four open passages.
+    pub crossroads: usize,
+    /// Cells without any open passage.
+    pub isolated: usize,
+    /// Average number of cells in a corridor, i.e. a maximal run of connected corridor cells.
+    pub average_corridor_length: f64,
+    /// Average number of cells in a dead-end branch, counted from the dead end up to, but not including,
+    /// the first junction. Mazes with a high river factor have few, long dead ends rather than many short ones.
+    pub river_factor: f64,
+    /// Number of cells on the shortest path from the start to the goal, or None if the goal is unreachable.
+    pub solution_length: Option<usize>,
+    /// Open passages between horizontal neighbors.
+    pub horizontal_passages: usize,
+    /// Open passages between vertical neighbors.
+    pub vertical_passages: usize,
+    /// Number of dead ends opening to the north, west, south and east, in that order. Reveals diagonal biases,
+    /// like that of a binary tree, which the passage counts are blind to.
+    pub dead_end_directions: [usize; 4],
+}
+
+impl MazeStats {
+    /// Returns the fraction of all cells that lie on the solution, or None if the goal is unreachable.
+    pub fn solution_ratio(&self) -> Option<f64> {
+        self.solution_length.map(|length| length as f64 / self.cell_count as f64)
+    }
+
+    /// Returns the fraction of open passages that are horizontal. An unbiased maze is close to 0.5.
+    pub fn horizontal_bias(&self) -> f64 {
+        let passages = self.horizontal_passages + self.vertical_passages;
+        if passages == 0 {
+            return 0.5;
+        }
+
+        self.horizontal_passages as f64 / passages as f64
+    }
+}
+
+impl fmt::Display for MazeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "cells: {}", self.cell_count)?;
+        writeln!(
+            f,
+            "dead ends: {}, corridors: {}, junctions: {}, crossroads: {}, isolated: {}",
+            self.dead_ends, self.corridors, self.junctions, self.crossroads, self.isolated
+        )?;
+        writeln!(f, "average corridor length: {:.2}", self.average_corridor_length)?;
+        writeln!(f, "river factor: {:.2}", self.river_factor)?;
+        match (self.solution_length, self.solution_ratio()) {
+            (Some(length), Some(ratio)) => writeln!(f, "solution length: {} ({:.1}% of cells)", length, ratio * 100.0)?,
+            _ => writeln!(f, "solution length: unreachable")?,
+        }
+        writeln!(
+            f,
+            "passages: {} horizontal, {} vertical ({:.1}% horizontal)",
+            self.horizontal_passages,
+            self.vertical_passages,
+            self.horizontal_bias() * 100.0
+        )?;
+        let [north, west, south, east] = self.dead_end_directions;
+        write!(f, "dead ends opening: {} north, {} west, {} south, {} east", north, west, south, east)
+    }
+}
+
+/// Analyzes *maze*, solving it from *start* to *goal* to measure the solution length.
+pub fn analyze(maze: &Maze, start: usize, goal: usize) -> MazeStats {
+    let neighbors: Vec<Vec<usize>> = (0..maze.cells.len()).map(|idx| get_open_neighbors(idx, maze)).collect();
+    let count_cells = |passages: usize| neighbors.iter().filter(|open| open.len() == passages).count();
+
+    let mut horizontal_passages = 0;
+    let mut vertical_passages = 0;
+    for cell in maze.cells.iter() {
+        //  Only look east and south, so every passage is counted once
+        if !cell.wall_east && get_neighbor(cell.idx, Dir::East, maze.width, maze.height).is_some() {
+            horizontal_passages += 1;
+        }
+        if !cell.wall_south && get_neighbor(cell.idx, Dir::South, maze.width, maze.height).is_some() {
+            vertical_passages += 1;
+        }
+    }
+
+    let mut dead_end_directions = [0; 4];
+    for (idx, open) in neighbors.iter().enumerate() {
+        if let [neighbor_idx] = open[..] {
+            let dir_idx = get_neighbors(idx, maze.width, maze.height)
+                .iter()
+                .position(|&neighbor| neighbor == Some(neighbor_idx))
+                .unwrap();
+            dead_end_directions[dir_idx] += 1;
+        }
+    }
+
+    MazeStats {
+        cell_count: maze.cells.len(),
+        dead_ends: count_cells(1),
+        corridors: count_cells(2),
+        junctions: count_cells(3),
+        crossroads: count_cells(4),
+        isolated: count_cells(0),
+        average_corridor_length: average_corridor_length(&neighbors),
+        river_factor: river_factor(&neighbors),
+        solution_length: DistanceMap::new(maze, start).distance(goal).map(|distance| distance + 1),
+        horizontal_passages,
+        vertical_passages,
+        dead_end_directions,
+    }
+}
+
+/// Returns the average size of the groups of connected corridor cells, given the open *neighbors* of every cell.
+fn average_corridor_length(neighbors: &[Vec<usize>]) -> f64 {
+    let mut visited = vec![false; neighbors.len()];
+    let mut corridor_count = 0;
+    let mut corridor_cells = 0;
+
+    for idx in 0..neighbors.len() {
+        if visited[idx] || neighbors[idx].len() != 2 {
+            continue;
+        }
+
+        corridor_count += 1;
+        visited[idx] = true;
+        let mut stack = vec![idx];
+        while let Some(cell_idx) = stack.pop() {
+            corridor_cells += 1;
+            for &neighbor_idx in neighbors[cell_idx].iter() {
+                if !visited[neighbor_idx] && neighbors[neighbor_idx].len() == 2 {
+                    visited[neighbor_idx] = true;
+                    stack.push(neighbor_idx);
+                }
+            }
+        }
+    }
+
+    if corridor_count == 0 {
+        return 0.0;
+    }
+
+    corridor_cells as f64 / corridor_count as f64
+}
+
+/// Returns the average length of the dead-end branches, given the open *neighbors* of every cell.
+fn river_factor(neighbors: &[Vec<usize>]) -> f64 {
+    let mut dead_end_count = 0;
+    let mut branch_cells = 0;
+
+    for idx in 0..neighbors.len() {
+        if neighbors[idx].len() != 1 {
+            continue;
+        }
+
+        //  Follow the branch until it reaches a junction, or another dead end in a maze that is a single path
+        dead_end_count += 1;
+        let (mut previous_idx, mut cell_idx) = (idx, neighbors[idx][0]);
+        branch_cells += 1;
+        while neighbors[cell_idx].len() == 2 {
+            let next_idx = if neighbors[cell_idx][0] == previous_idx { neighbors[cell_idx][1] } else { neighbors[cell_idx][0] };
+            previous_idx = cell_idx;
+            cell_idx = next_idx;
+            branch_cells += 1;
+        }
+        if neighbors[cell_idx].len() == 1 {
+            branch_cells += 1;
+        }
+    }
+
+    if dead_end_count == 0 {
+        return 0.0;
+    }
+
+    branch_cells as f64 / dead_end_count as f64
+}
@@ -2,6 +2,10 @@
 //  All rights reserved.
 
 use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Dir {
@@ -11,7 +15,34 @@ pub enum Dir {
     South
 }
 
-#[derive(Debug)]
+impl Dir {
+    /// Returns true for East and West, false for North and South.
+    pub fn is_horizontal(&self) -> bool {
+        *self == Dir::East || *self == Dir::West
+    }
+
+    /// Returns the direction 90 degrees counter clockwise of this one.
+    pub fn turn_left(&self) -> Dir {
+        match self {
+            Dir::North => Dir::West,
+            Dir::West => Dir::South,
+            Dir::South => Dir::East,
+            Dir::East => Dir::North,
+        }
+    }
+
+    /// Returns the direction 90 degrees clockwise of this one.
+    pub fn turn_right(&self) -> Dir {
+        self.opposite().turn_left()
+    }
+
+    /// Returns the direction opposite of this one.
+    pub fn opposite(&self) -> Dir {
+        self.turn_left().turn_left()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MazeCell {
     pub idx: usize,
     pub wall_north: bool,
@@ -27,6 +58,18 @@ pub struct Maze {
     pub cells: Vec<MazeCell>,
 }
 
+impl MazeCell {
+    /// Returns true if the cell has a wall in direction *dir*.
+    pub fn has_wall(&self, dir: Dir) -> bool {
+        match dir {
+            Dir::North => self.wall_north,
+            Dir::South => self.wall_south,
+            Dir::East => self.wall_east,
+            Dir::West => self.wall_west,
+        }
+    }
+}
+
 impl Maze {
     /// Constructs a empty maze.
     pub fn empty(width: usize, height: usize) -> Maze {
@@ -48,97 +91,1017 @@ impl Maze {
             cells,
         }
     }
+
+    /// Adds every wall, including the outer boundary, leaving each cell closed off from its neighbors.
+    pub fn fill_walls(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.wall_east = true;
+            cell.wall_west = true;
+            cell.wall_south = true;
+            cell.wall_north = true;
+        }
+    }
+
+    /// Checks that every wall is shared by both cells on either side of it, that every cell is reachable from
+    /// every other, and that there are no loops, i.e. that the maze is a perfect maze. Passages are only followed
+    /// where both cells agree there is no wall. Openings in the outer boundary are not violations.
+    pub fn validate(&self) -> ValidationReport {
+        let mut asymmetric_walls = vec![];
+        let mut loops = vec![];
+        let mut sets = DisjointSet::new(self.cells.len());
+
+        for cell in self.cells.iter() {
+            let (x, y) = to_x_y(cell.idx, self.width);
+
+            //  Only look east and south, so every pair of neighbors is checked once
+            for dir in [Dir::East, Dir::South] {
+                if let Some(nbor_idx) = get_neighbor(cell.idx, dir, self.width, self.height) {
+                    let wall = cell.has_wall(dir);
+                    if wall != self.cells[nbor_idx].has_wall(dir.opposite()) {
+                        asymmetric_walls.push(MazeViolation::AsymmetricWall { x, y, dir });
+                    } else if !wall && !sets.union(cell.idx, nbor_idx) {
+                        loops.push(MazeViolation::Loop { x, y, dir });
+                    }
+                }
+            }
+        }
+
+        let mut violations = asymmetric_walls;
+        violations.append(&mut loops);
+        for cell in self.cells.iter() {
+            if sets.find(cell.idx) != sets.find(0) {
+                let (x, y) = to_x_y(cell.idx, self.width);
+                violations.push(MazeViolation::Unreachable { x, y });
+            }
+        }
+
+        ValidationReport { violations }
+    }
+}
+
+/// A way in which a maze is not a perfect maze, found by [`Maze::validate`].
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MazeViolation {
+    /// The cell at (*x*, *y*) and its neighbor in direction *dir* disagree on whether there is a wall between them.
+    AsymmetricWall { x: usize, y: usize, dir: Dir },
+    /// The passage from the cell at (*x*, *y*) in direction *dir* connects two cells that were already connected,
+    /// closing a loop.
+    Loop { x: usize, y: usize, dir: Dir },
+    /// The cell at (*x*, *y*) cannot be reached from the top left cell.
+    Unreachable { x: usize, y: usize },
+}
+
+impl fmt::Display for MazeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeViolation::AsymmetricWall { x, y, dir } => {
+                write!(f, "({}, {}) and its {:?} neighbor disagree on the wall between them", x, y, dir)
+            }
+            MazeViolation::Loop { x, y, dir } => write!(f, "passage {:?} of ({}, {}) closes a loop", dir, x, y),
+            MazeViolation::Unreachable { x, y } => write!(f, "({}, {}) is unreachable from (0, 0)", x, y),
+        }
+    }
+}
+
+/// Every violation found by [`Maze::validate`]: asymmetric walls, then loops, then unreachable cells, each in
+/// order of cell index.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub violations: Vec<MazeViolation>,
+}
+
+impl ValidationReport {
+    /// Returns true if every wall is shared by the cells on both sides of it.
+    pub fn is_symmetric(&self) -> bool {
+        !self.violations.iter().any(|violation| matches!(violation, MazeViolation::AsymmetricWall { .. }))
+    }
+
+    /// Returns true if every cell is reachable from every other.
+    pub fn is_connected(&self) -> bool {
+        !self.violations.iter().any(|violation| matches!(violation, MazeViolation::Unreachable { .. }))
+    }
+
+    /// Returns true if there are no loops.
+    pub fn is_acyclic(&self) -> bool {
+        !self.violations.iter().any(|violation| matches!(violation, MazeViolation::Loop { .. }))
+    }
+
+    /// Returns true if the maze is a perfect maze: symmetric, connected and without loops, i.e. a tree.
+    pub fn is_perfect(&self) -> bool {
+        self.violations.is_empty()
+    }
 }
 
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.violations.is_empty() {
+            return write!(f, "perfect maze");
+        }
+
+        write!(f, "{} violations", self.violations.len())?;
+        for violation in self.violations.iter() {
+            write!(f, "\n{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Generator specific state of a cell that a visualizer may want to show while generation is ongoing.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CellHighlight {
+    /// The cell belongs to the group with the given id, e.g. a set of connected cells.
+    Group(usize),
+    /// The cell is part of the generator's tentative work, e.g. a random walk that has not been carved yet.
+    Active,
+}
+
+/// A maze generator, stepped one piece of work at a time so that generation can be visualized.
+/// Generators are seeded on construction: the same seed, settings and maze size always produce the same maze,
+/// or for endless generators the same sequence of mazes.
 pub trait MazeGenerator {
     /// Perform a step of the maze generator.
     fn step(&mut self, maze: &mut Maze);
     /// Returns true if the generation is complete. Othewerise false.
+    /// Endless generators never finish, see [`MazeGenerator::is_endless`].
     fn is_finished(&self) -> bool;
+
+    /// Returns true if the generator keeps mutating the maze for as long as it is stepped. Such a generator
+    /// never reports itself finished, instead the maze is complete after initialization and after every step.
+    fn is_endless(&self) -> bool {
+        false
+    }
     
     fn initialize(&mut self, maze: &mut Maze);
+
+    /// Returns how the cell with index *cell_idx* should be highlighted, if at all.
+    /// Generators without any interesting intermediate state need not implement this.
+    fn highlight(&self, _cell_idx: usize) -> Option<CellHighlight> {
+        None
+    }
 }
 
 pub struct RecursiveBacktracker {
     gen_iteration: usize,
     visited_stack: Vec<usize>,
     finished: bool,
+    rng: StdRng,
 }
 
 impl RecursiveBacktracker {
-    pub fn new() -> Self {
+    /// Construct a new RecursiveBacktracker maze generator.
+    pub fn new(seed: u64) -> Self {
         Self {
             gen_iteration: 0,
             finished: false,
-            visited_stack: vec![]
+            visited_stack: vec![],
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 }
 
 impl MazeGenerator for RecursiveBacktracker {
     fn step(&mut self, maze: &mut Maze) {
-        if self.visited_stack.is_empty() { // If the visited stack is empty and we arent on gen 0, we are done.
-            self.finished = true;
-            return;
+        //  Perform a step of the recursive backtracking algo.
+        //  If the most recent cell has no unvisited neighbors we backtrack to previous cells until we find one,
+        //  so that a step never simply pops the visited stack. This is a loop rather than a recursive call
+        //  since backtracks can be arbitrarily long in large mazes.
+        loop {
+            //  Get most recent cell. If the visited stack is empty, we are done.
+            let cell_idx = match self.visited_stack.pop() {
+                Some(cell_idx) => cell_idx,
+                None => {
+                    self.finished = true;
+                    return;
+                }
+            };
+
+            //  Fetch neighboring cells that havent been visited
+            let nbors: Vec<usize> = get_neighbors(cell_idx, maze.width, maze.height)
+                .iter()
+                .filter_map(|&x| {
+                    if let Some(x) = x {
+                        if !maze.cells[x].visited {
+                            Some(x)
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            if !nbors.is_empty() {
+                //  Add ourselves back into the stack to facillitate backtracking
+                self.visited_stack.push(cell_idx);
+
+                //  Carve out a path to a random unvisited neighbor and add neighbor to the stack
+                let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+                remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+                maze.cells[nbor_cell_idx].visited = true;
+                self.visited_stack.push(nbor_cell_idx);
+                self.gen_iteration += 1;
+                return;
+            }
         }
+    }
 
-        //  Perform a step of the recursive backtracking algo.
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
 
-        //  Get most recent cell
-        let cell_idx = self.visited_stack.pop().unwrap();
+        let start_idx = self.rng.gen_range(0..maze.cells.len());
+        maze.cells[start_idx].visited = true;
+        self.visited_stack.push(start_idx);
+    }
+}
+
+/// Randomized Prim's algorithm. Grows the maze from a single cell by repeatedly connecting a random
+/// frontier cell to the maze, which produces short and branchy corridors.
+pub struct Prim {
+    frontier: Vec<usize>,
+    in_frontier: Vec<bool>,
+    rng: StdRng,
+}
 
-        //  Fetch neighboring cells that havent been visited
+impl Prim {
+    /// Construct a new Prim maze generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            frontier: vec![],
+            in_frontier: vec![],
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Marks *cell_idx* as part of the maze and adds its unvisited neighbors to the frontier.
+    fn visit(&mut self, cell_idx: usize, maze: &mut Maze) {
+        maze.cells[cell_idx].visited = true;
+
+        for nbor_idx in get_neighbors(cell_idx, maze.width, maze.height).iter().flatten() {
+            if !maze.cells[*nbor_idx].visited && !self.in_frontier[*nbor_idx] {
+                self.in_frontier[*nbor_idx] = true;
+                self.frontier.push(*nbor_idx);
+            }
+        }
+    }
+}
+
+impl MazeGenerator for Prim {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        //  Take a random cell out of the frontier
+        let frontier_idx = self.rng.gen_range(0..self.frontier.len());
+        let cell_idx = self.frontier.swap_remove(frontier_idx);
+        self.in_frontier[cell_idx] = false;
+
+        //  Connect it to a random neighbor that is already part of the maze.
+        //  Every frontier cell is adjacent to at least one such cell.
         let nbors: Vec<usize> = get_neighbors(cell_idx, maze.width, maze.height)
             .iter()
-            .filter_map(|&x| {
-                if let Some(x) = x {
-                    let ret = if !maze.cells[x].visited {
-                        Some(x)
-                    } else {
-                        None
-                    };
+            .flatten()
+            .copied()
+            .filter(|&x| maze.cells[x].visited)
+            .collect();
+        let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+        remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
 
-                    ret
-                } else {
-                    None
+        self.visit(cell_idx, maze);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.frontier.is_empty()
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        self.frontier.clear();
+        self.in_frontier = vec![false; maze.cells.len()];
+
+        let start_idx = self.rng.gen_range(0..maze.cells.len());
+        self.visit(start_idx, maze);
+    }
+}
+
+/// Disjoint-set forest over cell indices, merged by rank.
+struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+        }
+    }
+
+    /// Returns the representative of the set containing *idx*.
+    /// Sets are merged by rank, so the trees stay shallow without path compression.
+    fn find(&self, mut idx: usize) -> usize {
+        while self.parent[idx] != idx {
+            idx = self.parent[idx];
+        }
+        idx
+    }
+
+    /// Merges the sets containing *a* and *b*. Returns false if they already were the same set.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.rank[a] < self.rank[b] {
+            self.parent[a] = b;
+        } else if self.rank[a] > self.rank[b] {
+            self.parent[b] = a;
+        } else {
+            self.parent[b] = a;
+            self.rank[a] += 1;
+        }
+        true
+    }
+
+    /// Returns true if *idx* is the only member of its set.
+    fn is_singleton(&self, idx: usize) -> bool {
+        self.parent[idx] == idx && self.rank[idx] == 0
+    }
+}
+
+/// Randomized Kruskal's algorithm. Removes walls in random order, as long as the wall separates
+/// two cells that are not yet connected, which produces a uniformly textured maze.
+pub struct Kruskal {
+    edges: Vec<(usize, usize)>,
+    sets: DisjointSet,
+    merges: usize,
+    rng: StdRng,
+}
+
+impl Kruskal {
+    /// Construct a new Kruskal maze generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            edges: vec![],
+            sets: DisjointSet::new(0),
+            merges: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl MazeGenerator for Kruskal {
+    fn step(&mut self, maze: &mut Maze) {
+        //  Pop edges until one connects two different sets, so that a step never does nothing.
+        while let Some((cell_idx, nbor_cell_idx)) = self.edges.pop() {
+            if self.sets.union(cell_idx, nbor_cell_idx) {
+                remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+                maze.cells[cell_idx].visited = true;
+                maze.cells[nbor_cell_idx].visited = true;
+                self.merges += 1;
+                return;
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        //  A spanning tree over n cells is complete after n - 1 merges.
+        self.merges + 1 >= self.sets.parent.len()
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        //  Collect every interior wall once, as the edge to its east or south neighbor
+        self.edges.clear();
+        for y in 0..maze.height {
+            for x in 0..maze.width {
+                let idx = to_idx(x, y, maze.width);
+                if x < maze.width - 1 {
+                    self.edges.push((idx, to_idx(x + 1, y, maze.width)));
                 }
-            })
+                if y < maze.height - 1 {
+                    self.edges.push((idx, to_idx(x, y + 1, maze.width)));
+                }
+            }
+        }
+        self.edges.shuffle(&mut self.rng);
+
+        self.sets = DisjointSet::new(maze.cells.len());
+        self.merges = 0;
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        if self.is_finished() || self.sets.is_singleton(cell_idx) {
+            return None;
+        }
+        Some(CellHighlight::Group(self.sets.find(cell_idx)))
+    }
+}
+
+/// Wilson's algorithm. Performs loop-erased random walks from cells outside the maze until they hit the
+/// maze, and then carves the walk. Produces a uniform spanning tree, i.e. every possible perfect maze
+/// is equally likely.
+pub struct Wilson {
+    /// The current loop-erased walk. Empty if no walk is in progress.
+    path: Vec<usize>,
+    /// Position of each cell in *path*, if it is on it.
+    path_pos: Vec<Option<usize>>,
+    /// Candidate start cells for walks, in random order.
+    unvisited: Vec<usize>,
+    finished: bool,
+    rng: StdRng,
+}
+
+impl Wilson {
+    /// Construct a new Wilson maze generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            path: vec![],
+            path_pos: vec![],
+            unvisited: vec![],
+            finished: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl MazeGenerator for Wilson {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.finished {
+            return;
+        }
+
+        //  Start a new walk from a cell that is not yet part of the maze
+        if self.path.is_empty() {
+            loop {
+                match self.unvisited.pop() {
+                    Some(cell_idx) if !maze.cells[cell_idx].visited => {
+                        self.path_pos[cell_idx] = Some(0);
+                        self.path.push(cell_idx);
+                        break;
+                    }
+                    Some(_) => continue,
+                    None => {
+                        self.finished = true;
+                        return;
+                    }
+                }
+            }
+        }
+
+        //  Move the walk to a random neighbor
+        let cell_idx = *self.path.last().unwrap();
+        let nbors: Vec<usize> = get_neighbors(cell_idx, maze.width, maze.height)
+            .iter()
+            .flatten()
+            .copied()
             .collect();
+        let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+
+        if maze.cells[nbor_cell_idx].visited {
+            //  The walk hit the maze, carve it out
+            self.path.push(nbor_cell_idx);
+            for pair in self.path.windows(2) {
+                remove_wall(pair[0], pair[1], maze.width, maze.cells.as_mut_slice());
+                maze.cells[pair[0]].visited = true;
+                self.path_pos[pair[0]] = None;
+            }
+            self.path.clear();
+        } else if let Some(pos) = self.path_pos[nbor_cell_idx] {
+            //  The walk ran into itself, erase the loop
+            for erased_idx in self.path.drain(pos + 1..) {
+                self.path_pos[erased_idx] = None;
+            }
+        } else {
+            self.path_pos[nbor_cell_idx] = Some(self.path.len());
+            self.path.push(nbor_cell_idx);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
 
-        if !nbors.is_empty() {
-            //  Add ourselves back into the stack to facillitate backtracking
-            self.visited_stack.push(cell_idx);
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
 
-            //  Carve out a path to a random unvisited neighbor and add neighbor to the stack
-            let index = self.gen_iteration % nbors.len();
-            let nbor_cell_idx = nbors[index];
-            remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+        self.path.clear();
+        self.path_pos = vec![None; maze.cells.len()];
+        self.unvisited = (0..maze.cells.len()).collect();
+        self.unvisited.shuffle(&mut self.rng);
+        self.finished = false;
+
+        //  The maze starts out as a single random cell
+        let root_idx = self.unvisited.pop().unwrap();
+        maze.cells[root_idx].visited = true;
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        self.path_pos.get(cell_idx).copied().flatten().map(|_| CellHighlight::Active)
+    }
+}
+
+/// Aldous-Broder algorithm. Random walks over the whole grid and carves into every cell the first time
+/// it is entered. Produces a uniform spanning tree, but needs a lot of walking to cover the last cells.
+pub struct AldousBroder {
+    curr_idx: usize,
+    remaining: usize,
+    walk_steps: usize,
+    rng: StdRng,
+}
+
+impl AldousBroder {
+    /// Construct a new AldousBroder maze generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            curr_idx: 0,
+            remaining: 0,
+            walk_steps: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the number of moves the random walk has made so far, including moves over cells that
+    /// were already part of the maze.
+    pub fn walk_steps(&self) -> usize {
+        self.walk_steps
+    }
+}
+
+impl MazeGenerator for AldousBroder {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        //  Walk to a random neighbor, carving into it if it hasnt been visited yet
+        let nbors: Vec<usize> = get_neighbors(self.curr_idx, maze.width, maze.height)
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+
+        if !maze.cells[nbor_cell_idx].visited {
+            remove_wall(self.curr_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
             maze.cells[nbor_cell_idx].visited = true;
-            self.visited_stack.push(nbor_cell_idx);
-            self.gen_iteration += 1;
+            self.remaining -= 1;
+        }
+
+        self.curr_idx = nbor_cell_idx;
+        self.walk_steps += 1;
+    }
+
+    fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        self.curr_idx = self.rng.gen_range(0..maze.cells.len());
+        maze.cells[self.curr_idx].visited = true;
+        self.remaining = maze.cells.len() - 1;
+        self.walk_steps = 0;
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        if !self.is_finished() && cell_idx == self.curr_idx {
+            Some(CellHighlight::Active)
         } else {
-            //  If we dont have any neighbors we backtrack to previous cell until we have one!
-            //  We do this here to avoid steps that simply pop the visited stack.
-            self.step(maze);
+            None
+        }
+    }
+}
+
+/// Streams the rows of an Eller's algorithm maze of fixed width, one row at a time, without ever holding
+/// more than a single row in memory. This allows for mazes of effectively infinite height.
+///
+/// Every row returned by [`EllerRows::next_row`] is left open towards the next row, so cells are only
+/// guaranteed to be connected once the maze is closed off with [`EllerRows::last_row`].
+/// Iterating yields an endless sequence of [`EllerRows::next_row`].
+pub struct EllerRows {
+    width: usize,
+    y: usize,
+    /// Set id of each cell in the current row.
+    sets: Vec<usize>,
+    /// Whether each cell in the current row was carved into from the row above.
+    open_north: Vec<bool>,
+    next_set: usize,
+    rng: StdRng,
+}
+
+impl EllerRows {
+    /// Construct a new row stream for mazes *width* cells wide.
+    /// The same *seed* and width always produce the same rows.
+    pub fn new(width: usize, seed: u64) -> Self {
+        Self {
+            width,
+            y: 0,
+            sets: vec![0; width],
+            open_north: vec![false; width],
+            next_set: 0,
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 
+    /// Returns the next row of the maze, with passages carved south into the row after it.
+    pub fn next_row(&mut self) -> Vec<MazeCell> {
+        self.row(false)
+    }
+
+    /// Returns the final row of the maze, which connects every remaining set and has no passages south.
+    /// Subsequent rows start a new, unconnected maze.
+    pub fn last_row(&mut self) -> Vec<MazeCell> {
+        self.row(true)
+    }
+
+    fn row(&mut self, last: bool) -> Vec<MazeCell> {
+        let width = self.width;
+
+        //  Cells that were not carved into from above start out in a set of their own
+        for x in 0..width {
+            if !self.open_north[x] {
+                self.sets[x] = self.next_set;
+                self.next_set += 1;
+            }
+        }
+
+        let mut cells: Vec<MazeCell> = (0..width)
+            .map(|x| MazeCell {
+                idx: to_idx(x, self.y, width),
+                wall_north: !self.open_north[x],
+                wall_south: true,
+                wall_east: true,
+                wall_west: true,
+                visited: true,
+            })
+            .collect();
+
+        //  Randomly join adjacent cells of different sets. The last row joins all of them to close the maze.
+        for x in 1..width {
+            if self.sets[x - 1] != self.sets[x] && (last || self.rng.gen::<bool>()) {
+                cells[x - 1].wall_east = false;
+                cells[x].wall_west = false;
+
+                let (kept, merged) = (self.sets[x - 1], self.sets[x]);
+                for set in self.sets.iter_mut().filter(|set| **set == merged) {
+                    *set = kept;
+                }
+            }
+        }
+
+        //  Carve south from at least one cell of every set, so that no set gets cut off
+        let mut open_south = vec![false; width];
+        if !last {
+            let mut order: Vec<usize> = (0..width).collect();
+            order.sort_by_key(|&x| self.sets[x]);
+
+            for members in order.chunk_by(|&a, &b| self.sets[a] == self.sets[b]) {
+                let mut carved = false;
+                for &x in members {
+                    if self.rng.gen::<bool>() {
+                        open_south[x] = true;
+                        carved = true;
+                    }
+                }
+
+                if !carved {
+                    open_south[members[self.rng.gen_range(0..members.len())]] = true;
+                }
+            }
+        }
+
+        for (cell, &open) in cells.iter_mut().zip(open_south.iter()) {
+            cell.wall_south = !open;
+        }
+
+        self.open_north = open_south;
+        self.y += 1;
+        cells
+    }
+}
+
+impl Iterator for EllerRows {
+    type Item = Vec<MazeCell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_row())
+    }
+}
+
+/// Eller's algorithm. Builds the maze one row at a time by merging sets of connected cells, see [`EllerRows`].
+pub struct Eller {
+    rows: EllerRows,
+    seed: u64,
+    rows_written: usize,
+    height: usize,
+}
+
+impl Eller {
+    /// Construct a new Eller maze generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rows: EllerRows::new(0, seed),
+            seed,
+            rows_written: 0,
+            height: 0,
+        }
+    }
+}
+
+impl MazeGenerator for Eller {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        let row = if self.rows_written == self.height - 1 {
+            self.rows.last_row()
+        } else {
+            self.rows.next_row()
+        };
+
+        let start = to_idx(0, self.rows_written, maze.width);
+        maze.cells[start..start + maze.width].clone_from_slice(&row);
+
+        //  Open up the row below where this row carves south right away, rather than once the next row is
+        //  written, so that neighbors agree on their walls between steps
+        if self.rows_written + 1 < self.height {
+            for idx in start..start + maze.width {
+                if !maze.cells[idx].wall_south {
+                    maze.cells[idx + maze.width].wall_north = false;
+                }
+            }
+        }
+        self.rows_written += 1;
+    }
+
+    fn is_finished(&self) -> bool {
+        self.rows_written == self.height
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        self.rows = EllerRows::new(maze.width, self.seed);
+        self.rows_written = 0;
+        self.height = maze.height;
+    }
+}
+
+/// Hunt-and-Kill algorithm. Random walks into unvisited cells until stuck, then hunts row by row for an
+/// unvisited cell next to the maze and continues walking from there. Produces long corridors like
+/// [`RecursiveBacktracker`] without having to remember the path walked.
+pub struct HuntAndKill {
+    curr_idx: usize,
+    /// The row being scanned if hunting, otherwise None.
+    hunt_row: Option<usize>,
+    /// All rows above this one are completely visited, so hunts can start here.
+    first_open_row: usize,
+    width: usize,
+    finished: bool,
+    rng: StdRng,
+}
+
+impl HuntAndKill {
+    /// Construct a new HuntAndKill maze generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            curr_idx: 0,
+            hunt_row: None,
+            first_open_row: 0,
+            width: 0,
+            finished: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the neighbors of *cell_idx* whose visited flag equals *visited*.
+    fn neighbors_where_visited(cell_idx: usize, visited: bool, maze: &Maze) -> Vec<usize> {
+        get_neighbors(cell_idx, maze.width, maze.height)
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|&x| maze.cells[x].visited == visited)
+            .collect()
+    }
+
+    /// Scans the current hunt row for an unvisited cell next to the maze, and connects it to the maze.
+    fn hunt(&mut self, maze: &mut Maze) {
+        let y = self.hunt_row.unwrap();
+        let mut row_visited = true;
+
+        for x in 0..maze.width {
+            let cell_idx = to_idx(x, y, maze.width);
+            if maze.cells[cell_idx].visited {
+                continue;
+            }
+            row_visited = false;
+
+            let nbors = Self::neighbors_where_visited(cell_idx, true, maze);
+            if !nbors.is_empty() {
+                //  Found one, carve into the maze and continue walking from here
+                let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+                remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+                maze.cells[cell_idx].visited = true;
+                self.curr_idx = cell_idx;
+                self.hunt_row = None;
+                return;
+            }
+        }
+
+        if row_visited && y == self.first_open_row {
+            self.first_open_row += 1;
+        }
+
+        if y + 1 == maze.height {
+            self.hunt_row = None;
+            self.finished = true;
+        } else {
+            self.hunt_row = Some(y + 1);
+        }
+    }
+}
+
+impl MazeGenerator for HuntAndKill {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.finished {
+            return;
+        }
+
+        if self.hunt_row.is_none() {
+            //  Kill: walk to a random unvisited neighbor
+            let nbors = Self::neighbors_where_visited(self.curr_idx, false, maze);
+            if !nbors.is_empty() {
+                let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+                remove_wall(self.curr_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+                maze.cells[nbor_cell_idx].visited = true;
+                self.curr_idx = nbor_cell_idx;
+                return;
+            }
+
+            //  Stuck, start hunting. The first row is scanned right away so that no step is wasted.
+            self.hunt_row = Some(self.first_open_row);
+        }
+
+        self.hunt(maze);
+    }
+
     fn is_finished(&self) -> bool {
         self.finished
     }
 
     fn initialize(&mut self, maze: &mut Maze) {
-        // Add all walls
-        for cell in maze.cells.iter_mut() {
-            cell.wall_east = true;
-            cell.wall_west = true;
-            cell.wall_south = true;
-            cell.wall_north = true;
+        maze.fill_walls();
+
+        self.curr_idx = self.rng.gen_range(0..maze.cells.len());
+        maze.cells[self.curr_idx].visited = true;
+        self.hunt_row = None;
+        self.first_open_row = 0;
+        self.width = maze.width;
+        self.finished = false;
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        if self.finished {
+            return None;
+        }
+
+        let is_active = match self.hunt_row {
+            Some(y) => to_x_y(cell_idx, self.width).1 == y,
+            None => cell_idx == self.curr_idx,
+        };
+        is_active.then_some(CellHighlight::Active)
+    }
+}
+
+/// Policy by which [`GrowingTree`] selects the next cell to grow from among its active cells.
+#[derive(PartialEq, Debug, Clone)]
+pub enum CellSelection {
+    /// The most recently added cell. Behaves like [`RecursiveBacktracker`], with long corridors.
+    Newest,
+    /// The least recently added cell. Produces long straight corridors radiating from the start.
+    Oldest,
+    /// A random cell. Behaves like [`Prim`], with short and branchy corridors.
+    Random,
+    /// The cell in the middle of the active list.
+    Middle,
+    /// Picks one of the given policies at random, in proportion to its weight. E.g. 75% newest / 25% random
+    /// is `Mix(vec![(CellSelection::Newest, 0.75), (CellSelection::Random, 0.25)])`.
+    /// Falls back to [`CellSelection::Newest`] if no policy has a positive weight.
+    Mix(Vec<(CellSelection, f64)>),
+}
+
+impl CellSelection {
+    /// Returns the index of the selected cell in an active list of length *len*, ordered from oldest to newest.
+    fn select(&self, len: usize, rng: &mut impl Rng) -> usize {
+        match self {
+            CellSelection::Newest => len - 1,
+            CellSelection::Oldest => 0,
+            CellSelection::Random => rng.gen_range(0..len),
+            CellSelection::Middle => len / 2,
+            CellSelection::Mix(policies) => {
+                let total: f64 = policies.iter().map(|(_, weight)| weight.max(0.0)).sum();
+                if total <= 0.0 {
+                    return CellSelection::Newest.select(len, rng);
+                }
+
+                let mut r = rng.gen_range(0.0..total);
+                for (policy, weight) in policies.iter().filter(|(_, weight)| *weight > 0.0) {
+                    if r < *weight {
+                        return policy.select(len, rng);
+                    }
+                    r -= weight;
+                }
+
+                //  Only reachable through floating point rounding
+                let (policy, _) = policies.iter().rev().find(|(_, weight)| *weight > 0.0).unwrap();
+                policy.select(len, rng)
+            }
         }
+    }
+}
+
+/// Growing Tree algorithm. Keeps a list of active cells, and repeatedly grows the maze from one of them
+/// as selected by a [`CellSelection`] policy. Cells without unvisited neighbors are removed from the list.
+pub struct GrowingTree {
+    selection: CellSelection,
+    /// Active cells, from oldest to newest.
+    active: VecDeque<usize>,
+    is_active: Vec<bool>,
+    rng: StdRng,
+}
 
-        let start_idx = rand::random::<usize>() % maze.cells.len();
+impl GrowingTree {
+    /// Construct a new GrowingTree maze generator, growing from cells picked by *selection*.
+    pub fn new(selection: CellSelection, seed: u64) -> Self {
+        Self {
+            selection,
+            active: VecDeque::new(),
+            is_active: vec![],
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl MazeGenerator for GrowingTree {
+    fn step(&mut self, maze: &mut Maze) {
+        //  Select active cells until one can be grown from, removing the ones that cant.
+        //  This way a step never simply shrinks the active list.
+        while !self.active.is_empty() {
+            let active_idx = self.selection.select(self.active.len(), &mut self.rng);
+            let cell_idx = self.active[active_idx];
+
+            let nbors: Vec<usize> = get_neighbors(cell_idx, maze.width, maze.height)
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|&x| !maze.cells[x].visited)
+                .collect();
+
+            if nbors.is_empty() {
+                self.active.remove(active_idx);
+                self.is_active[cell_idx] = false;
+                continue;
+            }
+
+            let nbor_cell_idx = nbors[self.rng.gen_range(0..nbors.len())];
+            remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+            maze.cells[nbor_cell_idx].visited = true;
+            self.active.push_back(nbor_cell_idx);
+            self.is_active[nbor_cell_idx] = true;
+            return;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        self.active.clear();
+        self.is_active = vec![false; maze.cells.len()];
+
+        let start_idx = self.rng.gen_range(0..maze.cells.len());
         maze.cells[start_idx].visited = true;
-        self.visited_stack.push(start_idx);
+        self.active.push_back(start_idx);
+        self.is_active[start_idx] = true;
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        self.is_active.get(cell_idx).copied().unwrap_or(false).then_some(CellHighlight::Active)
     }
 }
 
@@ -160,14 +1123,13 @@ struct FieldIntersection {
 impl Field {
     ///     Tries to subdivide itself into two fields. If *horizontally* is true it divides the field horizontally,
     ///     otherwise vertically.
-    pub fn divide(self, horizontally: bool) -> Option<(Field, Field, FieldIntersection)> {
+    pub fn divide(self, horizontally: bool, rng: &mut impl Rng) -> Option<(Field, Field, FieldIntersection)> {
         if self.width < 2 || self.height < 2 {
             return None;
         }
 
-        let r = rand::random::<usize>();
         if horizontally {
-            let split_coord = r % (self.height - 1);
+            let split_coord = rng.gen_range(0..self.height - 1);
 
             let top_field = Field {
                 x: self.x,
@@ -191,7 +1153,7 @@ impl Field {
 
             Some((top_field, bot_field, intersection))
         } else {
-            let split_coord = r % (self.width - 1);
+            let split_coord = rng.gen_range(0..self.width - 1);
 
             let left_field = Field {
                 x: self.x,
@@ -218,25 +1180,74 @@ impl Field {
     }
 }
 
+/// Tuning of how [`RecursiveDivision`] divides fields. The default settings produce a perfect maze.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct DivisionSettings {
+    /// Fields whose width and height are both at most this size are left undivided as open rooms.
+    pub min_room_size: usize,
+    /// Number of gaps left in every dividing wall. Clamped to at least one, and at most the length of the wall.
+    /// More than one gap introduces loops.
+    pub doorways: usize,
+    /// Probability in the range [0, 1] that a field is left undivided as an open room even though it could
+    /// be divided. Never applies to the initial field spanning the whole maze.
+    pub room_chance: f64,
+}
+
+impl Default for DivisionSettings {
+    fn default() -> Self {
+        Self {
+            min_room_size: 1,
+            doorways: 1,
+            room_chance: 0.0,
+        }
+    }
+}
+
 pub struct RecursiveDivision {
     gen_iteration: usize,
-    max_subdivides: usize,
+    max_subdivides: Option<usize>,
+    settings: DivisionSettings,
     fields: VecDeque<Field>,
+    rng: StdRng,
 }
 
 impl RecursiveDivision {
-    pub fn new(max_subdivides: usize) -> Self {
+    /// Construct a new RecursiveDivision maze generator.
+    /// Generation finishes once no field can be divided any further, or after *max_subdivides* iterations if given.
+    pub fn new(max_subdivides: Option<usize>, seed: u64) -> Self {
+        Self::with_settings(max_subdivides, DivisionSettings::default(), seed)
+    }
+
+    /// Construct a new RecursiveDivision maze generator that divides fields according to *settings*.
+    pub fn with_settings(max_subdivides: Option<usize>, settings: DivisionSettings, seed: u64) -> Self {
         Self {
             gen_iteration: 0,
             max_subdivides,
-            fields: VecDeque::new()
+            settings,
+            fields: VecDeque::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns true if *field* should be divided further, rather than be left as is.
+    /// Only fields for which this holds are queued, so that generation finishes once the queue runs dry.
+    fn should_divide(&mut self, field: &Field, is_whole_maze: bool) -> bool {
+        if field.width < 2 || field.height < 2 {
+            return false;
+        }
+
+        if field.width <= self.settings.min_room_size && field.height <= self.settings.min_room_size {
+            return false;
         }
+
+        //  The whole maze is never left as one room
+        is_whole_maze || self.settings.room_chance <= 0.0 || !self.rng.gen_bool(self.settings.room_chance.min(1.0))
     }
 }
 
 impl MazeGenerator for RecursiveDivision {
     fn step(&mut self, maze: &mut Maze) {
-        if self.gen_iteration == self.max_subdivides {
+        if self.is_finished() {
             return;
         }
 
@@ -249,6 +1260,7 @@ impl MazeGenerator for RecursiveDivision {
 
             //  Fetch oldest field
             let field = self.fields.pop_front().unwrap();
+            processed += 1;
 
             //  Determine if we should subdivide the field horizontally or vertically.
             //  If the field's height is larget than its width, we split it horizontally to
@@ -258,20 +1270,24 @@ impl MazeGenerator for RecursiveDivision {
             } else if field.width > field.height {
                 false
             } else {
-                rand::random::<usize>() % 2 == 0
+                self.rng.gen::<bool>()
             };
 
             //  Try to subdivide it
-            if let Some((field_1, field_2, intersection)) = field.divide(horiz) {
+            if let Some((field_1, field_2, intersection)) = field.divide(horiz, &mut self.rng) {
 
-                //  Choose where to make a gap in the wall
-                let skip = intersection.start + rand::random::<usize>() % (intersection.end-intersection.start);
+                //  Choose where to make gaps in the wall
+                let wall_len = intersection.end - intersection.start;
+                let mut is_gap = vec![false; wall_len];
+                for gap in rand::seq::index::sample(&mut self.rng, wall_len, self.settings.doorways.clamp(1, wall_len)) {
+                    is_gap[gap] = true;
+                }
 
                 //  Add wall to construct the division
                 if horiz {
                     let y = intersection.split_coord;
                     for x in intersection.start..intersection.end {
-                        if x == skip {
+                        if is_gap[x - intersection.start] {
                             continue;
                         }
 
@@ -283,7 +1299,7 @@ impl MazeGenerator for RecursiveDivision {
                 } else {
                     let x = intersection.split_coord;
                     for y in intersection.start..intersection.end {
-                        if y == skip {
+                        if is_gap[y - intersection.start] {
                             continue;
                         }
 
@@ -293,61 +1309,95 @@ impl MazeGenerator for RecursiveDivision {
                     }
                 }
 
-                //  Add the two newly created fields into the queue
-                self.fields.push_back(field_1);
-                self.fields.push_back(field_2);
+                //  Add the two newly created fields into the queue, unless they are done
+                for new_field in [field_1, field_2] {
+                    if self.should_divide(&new_field, false) {
+                        self.fields.push_back(new_field);
+                    }
+                }
             }
-            processed += 1;
         }
 
         self.gen_iteration += 1;
     }
 
     fn is_finished(&self) -> bool {
-        self.gen_iteration == self.max_subdivides
+        self.fields.is_empty() || Some(self.gen_iteration) == self.max_subdivides
     }
 
     fn initialize(&mut self, maze: &mut Maze) {
-        //  Remove all walls
+        //  Remove all walls, except for the outer boundary
         for cell in maze.cells.iter_mut() {
-            cell.wall_east = false;
-            cell.wall_west = false;
-            cell.wall_south = false;
-            cell.wall_north = false;
+            let (x, y) = to_x_y(cell.idx, maze.width);
+            cell.wall_east = x == maze.width - 1;
+            cell.wall_west = x == 0;
+            cell.wall_south = y == maze.height - 1;
+            cell.wall_north = y == 0;
         }
 
-        self.fields.push_front(Field {
+        self.gen_iteration = 0;
+        self.fields.clear();
+
+        let field = Field {
             x: 0,
             y: 0,
             width: maze.width,
             height: maze.height
-        });
+        };
+        if self.should_divide(&field, true) {
+            self.fields.push_back(field);
+        }
+    }
+}
+
+/// Reasons a pair of directions is not a valid [`BinaryTree`] bias.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BinaryTreeError {
+    /// Both directions are the same direction.
+    SameDirection(Dir),
+    /// The directions are opposite each other, along the same axis.
+    SameAxis(Dir, Dir),
+}
+
+impl fmt::Display for BinaryTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryTreeError::SameDirection(dir) => write!(f, "both directions are {:?}", dir),
+            BinaryTreeError::SameAxis(dir_1, dir_2) => write!(f, "{:?} and {:?} are along the same axis", dir_1, dir_2),
+        }
     }
 }
 
+impl std::error::Error for BinaryTreeError {}
+
 pub struct BinaryTree {
     curr_idx: usize,
     end_idx: usize,
     dir_1: Dir,
-    dir_2: Dir
+    dir_2: Dir,
+    rng: StdRng,
 }
 
 impl BinaryTree {
-    /// Construct a new BinaryTree maze generator.
-    /// Note: Currently no direction validation is done, except for if dir_1 == dir_2
-    /// So choosing direction along the same axis is allowed, but produces very boring mazes.
-    pub fn new(dir_1: Dir, dir_2: Dir) -> Self {
-
+    /// Construct a new BinaryTree maze generator, carving either towards *dir_1* or *dir_2* from every cell.
+    /// The directions must be perpendicular, i.e. form one of the four diagonal biases. Directions along the
+    /// same axis would produce a maze that is not connected.
+    pub fn new(dir_1: Dir, dir_2: Dir, seed: u64) -> Result<Self, BinaryTreeError> {
         if dir_1 == dir_2 {
-            panic!("dir_1 and dir_2 must not be equal!");
+            return Err(BinaryTreeError::SameDirection(dir_1));
         }
 
-        Self {
+        if dir_1.is_horizontal() == dir_2.is_horizontal() {
+            return Err(BinaryTreeError::SameAxis(dir_1, dir_2));
+        }
+
+        Ok(Self {
             curr_idx: 0,
             end_idx: 0,
             dir_1,
             dir_2,
-        }
+            rng: StdRng::seed_from_u64(seed),
+        })
     }
 }
 
@@ -357,8 +1407,7 @@ impl MazeGenerator for BinaryTree {
             return;
         }
 
-        let r = rand::random::<usize>() % 2;
-        let (mut dir, other_dir) = if r == 0 {
+        let (mut dir, other_dir) = if self.rng.gen::<bool>() {
             (self.dir_1, self.dir_2)
         } else {
             (self.dir_2, self.dir_1)
@@ -367,15 +1416,7 @@ impl MazeGenerator for BinaryTree {
         let (x,y) = to_x_y(self.curr_idx, maze.width);
         
         //  Bound check if we are on the boundary
-        if dir == Dir::North && y == 0 {
-            if (x == 0 && other_dir == Dir::West) || (x == maze.width - 1 && other_dir == Dir::East) {
-                self.curr_idx += 1;
-                return;
-            } else {
-                dir = other_dir;
-            }
-        }
-        else if dir == Dir::South && y == maze.height - 1 {
+        if (dir == Dir::North && y == 0) || (dir == Dir::South && y == maze.height - 1) {
             if (x == 0 && other_dir == Dir::West) || (x == maze.width - 1 && other_dir == Dir::East) {
                 self.curr_idx += 1;
                 return;
@@ -383,15 +1424,7 @@ impl MazeGenerator for BinaryTree {
                 dir = other_dir;
             }
         }
-        else if dir == Dir::West && x == 0 {
-            if (y == 0 && other_dir == Dir::North) || (y == maze.height - 1 && other_dir == Dir::South) {
-                self.curr_idx += 1;
-                return;
-            } else {
-                dir = other_dir
-            }
-        }
-        else if dir == Dir::East && x == maze.width - 1 {
+        else if (dir == Dir::West && x == 0) || (dir == Dir::East && x == maze.width - 1) {
             if (y == 0 && other_dir == Dir::North) || (y == maze.height - 1 && other_dir == Dir::South) {
                 self.curr_idx += 1;
                 return;
@@ -417,14 +1450,471 @@ impl MazeGenerator for BinaryTree {
     fn initialize(&mut self, maze: &mut Maze) {
         self.end_idx = maze.cells.len()-1;
 
-        // Add all walls
+        maze.fill_walls();
+    }
+}
+
+/// Sidewinder algorithm. Processes the maze line by line, carving runs of cells along the line and closing
+/// each run by carving from a random cell of it towards the *bias* direction. With a North bias the lines
+/// are rows, runs go east and are closed north. The line along the boundary in the bias direction becomes one
+/// single open corridor.
+pub struct Sidewinder {
+    bias: Dir,
+    width: usize,
+    height: usize,
+    /// Index of the line being processed, counted from the line along the biased boundary.
+    line: usize,
+    /// Position of the next cell to process along the current line.
+    pos: usize,
+    /// Position along the current line where the current run starts.
+    run_start: usize,
+    rng: StdRng,
+}
+
+impl Sidewinder {
+    /// Construct a new Sidewinder maze generator, closing runs towards *bias*.
+    pub fn new(bias: Dir, seed: u64) -> Self {
+        Self {
+            bias,
+            width: 0,
+            height: 0,
+            line: 0,
+            pos: 0,
+            run_start: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns true if the lines are rows, otherwise they are columns.
+    fn lines_are_rows(&self) -> bool {
+        self.bias == Dir::North || self.bias == Dir::South
+    }
+
+    /// Returns (line count, line length).
+    fn line_dims(&self) -> (usize, usize) {
+        if self.lines_are_rows() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Returns the x,y coordinate of the cell at *pos* along *line*.
+    fn to_x_y(&self, line: usize, pos: usize) -> (usize, usize) {
+        let (line_count, _) = self.line_dims();
+
+        //  Lines are processed starting from the biased boundary
+        let line = match self.bias {
+            Dir::North | Dir::West => line,
+            Dir::South | Dir::East => line_count - 1 - line,
+        };
+
+        if self.lines_are_rows() {
+            (pos, line)
+        } else {
+            (line, pos)
+        }
+    }
+}
+
+impl MazeGenerator for Sidewinder {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        let (_, line_len) = self.line_dims();
+        let (x, y) = self.to_x_y(self.line, self.pos);
+        let cell_idx = to_idx(x, y, maze.width);
+        maze.cells[cell_idx].visited = true;
+
+        let at_line_end = self.pos == line_len - 1;
+        //  The first line has nothing to close runs towards, so it is one single run
+        let close_run = self.line > 0 && (at_line_end || self.rng.gen::<bool>());
+
+        if close_run {
+            //  Carve towards the bias from a random cell of the run, and start a new run
+            let run_pos = self.rng.gen_range(self.run_start..=self.pos);
+            let (run_x, run_y) = self.to_x_y(self.line, run_pos);
+            let (nbor_x, nbor_y) = match self.bias {
+                Dir::North => (run_x, run_y - 1),
+                Dir::South => (run_x, run_y + 1),
+                Dir::East => (run_x + 1, run_y),
+                Dir::West => (run_x - 1, run_y)
+            };
+            remove_wall(to_idx(run_x, run_y, maze.width), to_idx(nbor_x, nbor_y, maze.width), maze.width, maze.cells.as_mut_slice());
+            self.run_start = self.pos + 1;
+        } else if !at_line_end {
+            //  Extend the run along the line
+            let (nbor_x, nbor_y) = self.to_x_y(self.line, self.pos + 1);
+            remove_wall(cell_idx, to_idx(nbor_x, nbor_y, maze.width), maze.width, maze.cells.as_mut_slice());
+        }
+
+        self.pos += 1;
+        if self.pos == line_len {
+            self.line += 1;
+            self.pos = 0;
+            self.run_start = 0;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        let (line_count, _) = self.line_dims();
+        self.line == line_count
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        self.width = maze.width;
+        self.height = maze.height;
+        self.line = 0;
+        self.pos = 0;
+        self.run_start = 0;
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        if self.is_finished() {
+            return None;
+        }
+
+        //  The current run consists of the cells processed since it started
+        (self.run_start..self.pos)
+            .any(|pos| {
+                let (x, y) = self.to_x_y(self.line, pos);
+                to_idx(x, y, self.width) == cell_idx
+            })
+            .then_some(CellHighlight::Active)
+    }
+}
+
+/// A Life-like cellular automaton rule, e.g. B3/S23 for Conway's Game of Life. A dead cell becomes alive if
+/// its number of alive neighbors is one of the birth counts, and an alive cell stays alive if its number of
+/// alive neighbors is one of the survival counts.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LifeRule {
+    pub birth: [bool; 9],
+    pub survival: [bool; 9],
+}
+
+impl LifeRule {
+    /// B3/S12345, grows maze-like corridors.
+    pub const MAZE: LifeRule = LifeRule::from_counts(&[3], &[1, 2, 3, 4, 5]);
+    /// B678/S345678, smooths noise into open caves.
+    pub const CAVE: LifeRule = LifeRule::from_counts(&[6, 7, 8], &[3, 4, 5, 6, 7, 8]);
+
+    const fn from_counts(birth_counts: &[usize], survival_counts: &[usize]) -> LifeRule {
+        let mut rule = LifeRule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+
+        let mut i = 0;
+        while i < birth_counts.len() {
+            rule.birth[birth_counts[i]] = true;
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < survival_counts.len() {
+            rule.survival[survival_counts[i]] = true;
+            i += 1;
+        }
+        rule
+    }
+}
+
+impl fmt::Display for LifeRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for count in (0..9).filter(|&count| self.birth[count]) {
+            write!(f, "{}", count)?;
+        }
+        write!(f, "/S")?;
+        for count in (0..9).filter(|&count| self.survival[count]) {
+            write!(f, "{}", count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`LifeRule`] from a string that is not in B/S notation.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseRuleError {
+    rule: String,
+}
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" is not a rule in B/S notation, e.g. B3/S12345", self.rule)
+    }
+}
+
+impl std::error::Error for ParseRuleError {}
+
+impl FromStr for LifeRule {
+    type Err = ParseRuleError;
+
+    /// Parses a rule in B/S notation, e.g. "B3/S12345". The parts may come in any order and are case insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseRuleError { rule: s.to_string() };
+
+        let mut birth = None;
+        let mut survival = None;
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let target = match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') => &mut birth,
+                Some('S') => &mut survival,
+                _ => return Err(err()),
+            };
+
+            if target.is_some() {
+                return Err(err());
+            }
+
+            let mut counts = [false; 9];
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(count) if count <= 8 => counts[count as usize] = true,
+                    _ => return Err(err()),
+                }
+            }
+            *target = Some(counts);
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(LifeRule { birth, survival }),
+            _ => Err(err()),
+        }
+    }
+}
+
+/// Cellular automaton cave generator. Randomly fills the grid with alive cells and then evolves it by a
+/// [`LifeRule`], one generation per step. Alive cells are solid rock, walled off from all sides, while
+/// dead cells are open floor. Unlike the other generators the result is generally not a perfect maze.
+pub struct CellularAutomaton {
+    rule: LifeRule,
+    fill_chance: f64,
+    max_generations: usize,
+    generation: usize,
+    alive: Vec<bool>,
+    finished: bool,
+    rng: StdRng,
+}
+
+impl CellularAutomaton {
+    /// Construct a new CellularAutomaton maze generator. Every cell starts out alive with probability
+    /// *fill_chance*, and the automaton runs for at most *max_generations*, stopping early once it is stable.
+    pub fn new(rule: LifeRule, fill_chance: f64, max_generations: usize, seed: u64) -> Self {
+        Self {
+            rule,
+            fill_chance: fill_chance.clamp(0.0, 1.0),
+            max_generations,
+            generation: 0,
+            alive: vec![],
+            finished: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the number of alive cells among the 8 cells surrounding x,y. Cells outside the maze are dead.
+    fn alive_neighbors(&self, x: usize, y: usize, width: usize, height: usize) -> usize {
+        let mut count = 0;
+        for nbor_y in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+            for nbor_x in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                if (nbor_x, nbor_y) != (x, y) && self.alive[to_idx(nbor_x, nbor_y, width)] {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Walls off every alive cell from its neighbors and opens up the space between dead cells.
+    fn apply_walls(&self, maze: &mut Maze) {
+        for idx in 0..maze.cells.len() {
+            let (x, y) = to_x_y(idx, maze.width);
+            maze.cells[idx].visited = !self.alive[idx];
+
+            //  Only consider east and south, every wall is shared with the neighbor on the other side
+            if x < maze.width - 1 {
+                let nbor_idx = to_idx(x + 1, y, maze.width);
+                if self.alive[idx] || self.alive[nbor_idx] {
+                    add_wall(idx, nbor_idx, maze.width, maze.cells.as_mut_slice());
+                } else {
+                    remove_wall(idx, nbor_idx, maze.width, maze.cells.as_mut_slice());
+                }
+            }
+            if y < maze.height - 1 {
+                let nbor_idx = to_idx(x, y + 1, maze.width);
+                if self.alive[idx] || self.alive[nbor_idx] {
+                    add_wall(idx, nbor_idx, maze.width, maze.cells.as_mut_slice());
+                } else {
+                    remove_wall(idx, nbor_idx, maze.width, maze.cells.as_mut_slice());
+                }
+            }
+        }
+    }
+}
+
+impl MazeGenerator for CellularAutomaton {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        let next: Vec<bool> = (0..maze.cells.len())
+            .map(|idx| {
+                let (x, y) = to_x_y(idx, maze.width);
+                let count = self.alive_neighbors(x, y, maze.width, maze.height);
+                if self.alive[idx] {
+                    self.rule.survival[count]
+                } else {
+                    self.rule.birth[count]
+                }
+            })
+            .collect();
+
+        //  A stable grid will never change again
+        if next == self.alive {
+            self.finished = true;
+            return;
+        }
+
+        self.alive = next;
+        self.generation += 1;
+        self.apply_walls(maze);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished || self.generation == self.max_generations
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
+
+        let fill_chance = self.fill_chance;
+        self.alive = (0..maze.cells.len()).map(|_| self.rng.gen_bool(fill_chance)).collect();
+        self.generation = 0;
+        self.finished = false;
+        self.apply_walls(maze);
+    }
+}
+
+/// Origin shift algorithm. Maintains the maze as a spanning tree where every cell points towards its parent,
+/// all the way to a root cell called the origin. Every shift moves the origin to a random neighbor, making the
+/// old origin point to it, which removes one passage and carves another. The maze is perfect after every shift,
+/// and keeps changing for as long as the generator is stepped.
+pub struct OriginShift {
+    shifts_per_step: usize,
+    /// The parent of every cell, None for the origin.
+    parents: Vec<Option<usize>>,
+    origin: usize,
+    rng: StdRng,
+}
+
+impl OriginShift {
+    /// Number of shifts per cell performed when initializing, to start out from a well mixed maze.
+    const WARMUP_SHIFTS_PER_CELL: usize = 10;
+
+    /// Construct a new OriginShift maze generator, shifting the origin *shifts_per_step* times per step.
+    pub fn new(shifts_per_step: usize, seed: u64) -> Self {
+        Self {
+            shifts_per_step: shifts_per_step.max(1),
+            parents: vec![],
+            origin: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Moves the origin to a random neighbor.
+    fn shift(&mut self, maze: &mut Maze) {
+        let nbors: Vec<usize> = get_neighbors(self.origin, maze.width, maze.height)
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+        if nbors.is_empty() {
+            return;
+        }
+        let new_origin = nbors[self.rng.gen_range(0..nbors.len())];
+
+        //  The new origin loses the passage to its parent, unless that parent is the old origin,
+        //  in which case the passage is kept but reversed.
+        if let Some(old_parent) = self.parents[new_origin] {
+            if old_parent != self.origin {
+                add_wall(new_origin, old_parent, maze.width, maze.cells.as_mut_slice());
+            }
+        }
+        remove_wall(self.origin, new_origin, maze.width, maze.cells.as_mut_slice());
+
+        self.parents[self.origin] = Some(new_origin);
+        self.parents[new_origin] = None;
+        self.origin = new_origin;
+    }
+}
+
+impl MazeGenerator for OriginShift {
+    fn step(&mut self, maze: &mut Maze) {
+        if self.parents.is_empty() {
+            return;
+        }
+
+        for _ in 0..self.shifts_per_step {
+            self.shift(maze);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    fn is_endless(&self) -> bool {
+        true
+    }
+
+    fn initialize(&mut self, maze: &mut Maze) {
+        maze.fill_walls();
         for cell in maze.cells.iter_mut() {
-            cell.wall_east = true;
-            cell.wall_west = true;
-            cell.wall_south = true;
-            cell.wall_north = true;
+            cell.visited = true;
+        }
+
+        //  Start out from every cell pointing east, and the east-most column pointing south,
+        //  with the origin in the south-east corner.
+        self.parents = (0..maze.cells.len())
+            .map(|idx| {
+                let (x, y) = to_x_y(idx, maze.width);
+                if x < maze.width - 1 {
+                    Some(to_idx(x + 1, y, maze.width))
+                } else if y < maze.height - 1 {
+                    Some(to_idx(x, y + 1, maze.width))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        self.origin = maze.cells.len() - 1;
+
+        for idx in 0..maze.cells.len() {
+            if let Some(parent) = self.parents[idx] {
+                remove_wall(idx, parent, maze.width, maze.cells.as_mut_slice());
+            }
+        }
+
+        for _ in 0..maze.cells.len() * Self::WARMUP_SHIFTS_PER_CELL {
+            self.shift(maze);
         }
     }
+
+    fn highlight(&self, cell_idx: usize) -> Option<CellHighlight> {
+        (!self.parents.is_empty() && cell_idx == self.origin).then_some(CellHighlight::Active)
+    }
 }
 
 /// Removes wall between cell_idx and nbor_cell_idx
@@ -516,4 +2006,108 @@ pub fn get_neighbors(cell_idx: usize, width: usize, height: usize) -> [Option<us
         south,
         east,
     ]
-}
\ No newline at end of file
+}
+
+/// Returns the index of the neighbor of cell with index *cell_idx* in direction *dir*, if it is inside the maze.
+pub fn get_neighbor(cell_idx: usize, dir: Dir, width: usize, height: usize) -> Option<usize> {
+    let [north, west, south, east] = get_neighbors(cell_idx, width, height);
+    match dir {
+        Dir::North => north,
+        Dir::West => west,
+        Dir::South => south,
+        Dir::East => east,
+    }
+}
+
+/// Returns the directions in which the cell with index *cell_idx* lies on the outer boundary of the maze.
+pub fn get_boundary_dirs(cell_idx: usize, width: usize, height: usize) -> Vec<Dir> {
+    [Dir::North, Dir::West, Dir::South, Dir::East]
+        .iter()
+        .copied()
+        .filter(|&dir| get_neighbor(cell_idx, dir, width, height).is_none())
+        .collect()
+}
+
+/// Opens up the outer boundary wall of the cell with index *cell_idx*, e.g. to make it an entrance.
+/// A corner cell is opened in the first of its boundary directions in CCW order starting from north.
+/// Returns false if the cell is not on the boundary.
+pub fn open_boundary(maze: &mut Maze, cell_idx: usize) -> bool {
+    let dir = match get_boundary_dirs(cell_idx, maze.width, maze.height).first() {
+        Some(&dir) => dir,
+        None => return false,
+    };
+
+    let cell = &mut maze.cells[cell_idx];
+    match dir {
+        Dir::North => cell.wall_north = false,
+        Dir::West => cell.wall_west = false,
+        Dir::South => cell.wall_south = false,
+        Dir::East => cell.wall_east = false,
+    }
+    true
+}
+
+/// Returns the neighbor indices of cell with index *cell_idx* that can be reached without passing through a wall.
+/// Returns in the same order as [`get_neighbors`].
+pub fn get_open_neighbors(cell_idx: usize, maze: &Maze) -> Vec<usize> {
+    let cell = &maze.cells[cell_idx];
+    let open = [!cell.wall_north, !cell.wall_west, !cell.wall_south, !cell.wall_east];
+
+    get_neighbors(cell_idx, maze.width, maze.height)
+        .iter()
+        .zip(open.iter())
+        .filter_map(|(&nbor, &open)| if open { nbor } else { None })
+        .collect()
+}
+
+/// Braids the maze by removing a *fraction* in the range [0, 1] of its dead ends, which introduces loops.
+/// Every removed dead end has a wall knocked out towards one of its neighbors, preferably towards another
+/// dead end so that both are removed at once, as long as that does not remove more than the fraction asked for.
+/// Returns the number of dead ends removed.
+/// The same *seed* and maze always produce the same braided maze.
+pub fn braid(maze: &mut Maze, fraction: f64, seed: u64) -> usize {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let is_dead_end = |cell_idx: usize, maze: &Maze| get_open_neighbors(cell_idx, maze).len() == 1;
+
+    let mut dead_ends: Vec<usize> = (0..maze.cells.len()).filter(|&idx| is_dead_end(idx, maze)).collect();
+    let target = (dead_ends.len() as f64 * fraction.clamp(0.0, 1.0)).round() as usize;
+    dead_ends.shuffle(&mut rng);
+
+    let mut removed = 0;
+    for cell_idx in dead_ends {
+        if removed >= target {
+            break;
+        }
+
+        //  An earlier removal may have connected this dead end already
+        if !is_dead_end(cell_idx, maze) {
+            continue;
+        }
+
+        let open = get_open_neighbors(cell_idx, maze);
+        let walled: Vec<usize> = get_neighbors(cell_idx, maze.width, maze.height)
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|nbor_idx| !open.contains(nbor_idx))
+            .collect();
+        if walled.is_empty() {
+            continue;
+        }
+
+        //  Joining two dead ends removes both, so only do so while that does not overshoot the target
+        let (dead_end_nbors, other_nbors): (Vec<usize>, Vec<usize>) =
+            walled.iter().partition(|&&idx| is_dead_end(idx, maze));
+        let joins_dead_ends = !dead_end_nbors.is_empty() && removed + 2 <= target;
+        let candidates = if joins_dead_ends { &dead_end_nbors } else { &other_nbors };
+        if candidates.is_empty() {
+            continue;
+        }
+        let nbor_cell_idx = candidates[rng.gen_range(0..candidates.len())];
+
+        remove_wall(cell_idx, nbor_cell_idx, maze.width, maze.cells.as_mut_slice());
+        removed += if joins_dead_ends { 2 } else { 1 };
+    }
+
+    removed
+}
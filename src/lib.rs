@@ -0,0 +1,26 @@
+//  Copyright (c) Klas Henriksson 2022.
+//  All rights reserved.
+
+//! Maze generation library.
+//!
+//! The generators in [`gen`] and the solvers in [`solver`] operate on a plain [`Maze`] and do not depend
+//! on any windowing, so they can be driven headless. [`viz`] rasterizes a maze into a software framebuffer
+//! which the `mazegen` binary presents in a minifb window. [`analysis`] measures the structure of finished mazes,
+//! to compare generators by more than their looks.
+
+pub mod analysis;
+pub mod gen;
+pub mod solver;
+pub mod viz;
+
+pub use analysis::{analyze, MazeStats};
+pub use gen::{
+    braid, get_open_neighbors, open_boundary, AldousBroder, BinaryTree, BinaryTreeError, CellHighlight, CellSelection,
+    CellularAutomaton, Dir, DivisionSettings, Eller, EllerRows, GrowingTree, HuntAndKill, Kruskal, LifeRule, Maze,
+    MazeCell, MazeGenerator, MazeViolation, OriginShift, ParseRuleError, Prim, RecursiveBacktracker, RecursiveDivision,
+    Sidewinder, ValidationReport, Wilson,
+};
+pub use solver::{
+    longest_path_endpoints, place_start_and_goal, solve, AStar, BreadthFirst, DeadEndFilling, DistanceMap, Hand,
+    Heuristic, MazeSolver, SolverHighlight, Tremaux, WalkOutcome, WallFollower,
+};
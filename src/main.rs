@@ -1,12 +1,11 @@
 //  Copyright (c) Klas Henriksson 2022.
 //  All rights reserved.
 
-mod gen;
-mod viz;
-
-use gen::{Maze, RecursiveBacktracker, MazeGenerator, RecursiveDivision, BinaryTree};
+use mazegen::analysis;
+use mazegen::gen::{self, Maze, RecursiveBacktracker, MazeGenerator, RecursiveDivision, DivisionSettings, BinaryTree, Prim, Kruskal, Wilson, AldousBroder, Eller, Sidewinder, HuntAndKill, GrowingTree, CellSelection, CellularAutomaton, OriginShift};
+use mazegen::solver::{self, AStar, BreadthFirst, DeadEndFilling, DistanceMap, Hand, Heuristic, MazeSolver, Tremaux, WalkOutcome, WallFollower};
+use mazegen::viz::{self, MazeVizDescritptor, Framebuffer};
 use minifb::{Key, Window, WindowOptions};
-use viz::{MazeVizDescritptor, Framebuffer};
 
 const WIDTH: usize = 640*2;
 const HEIGHT: usize = 360*2;
@@ -15,17 +14,91 @@ const HEIGHT: usize = 360*2;
 enum GeneratorType {
     RecursiveBacktracker,
     RecursiveDivision,
-    BinaryTree
+    /// Recursive division leaving open rooms and multiple doorways.
+    Dungeon,
+    /// Binary tree carving towards either of the two directions in *bias*.
+    BinaryTree { bias: (gen::Dir, gen::Dir) },
+    Prim,
+    Kruskal,
+    Wilson,
+    AldousBroder,
+    Eller,
+    Sidewinder,
+    HuntAndKill,
+    /// Growing tree picking the newest cell *newest_percent* % of the time, and a random cell otherwise.
+    GrowingTree { newest_percent: u32 },
+    /// Cellular automaton evolving by the life-like *rule*, in B/S notation.
+    CellularAutomaton { rule: &'static str },
+    /// Endless origin shift, a living maze whose walls keep moving.
+    OriginShift,
+}
+
+/// Rules the cellular automaton cycles through: Maze, Mazectric and a cave smoothing rule.
+const AUTOMATON_RULES: [&str; 3] = ["B3/S12345", "B3/S1234", "B678/S345678"];
+
+#[derive(Debug, Clone, Copy)]
+enum SolverType {
+    BreadthFirst,
+    AStar(Heuristic),
+    WallFollower(Hand),
+    Tremaux,
+    DeadEndFilling,
 }
 
-fn create_generator(ty: GeneratorType) -> Box<dyn MazeGenerator> {
+fn create_solver(ty: SolverType, seed: u64) -> Box<dyn MazeSolver> {
     match ty {
-        GeneratorType::RecursiveBacktracker => Box::new(RecursiveBacktracker::new()),
-        GeneratorType::RecursiveDivision => Box::new(RecursiveDivision::new(100)),
-        GeneratorType::BinaryTree => Box::new(BinaryTree::new(gen::Dir::North, gen::Dir::East)),
+        SolverType::BreadthFirst => Box::new(BreadthFirst::new()),
+        SolverType::AStar(heuristic) => Box::new(AStar::new(heuristic)),
+        SolverType::WallFollower(hand) => Box::new(WallFollower::new(hand)),
+        SolverType::Tremaux => Box::new(Tremaux::new(seed)),
+        SolverType::DeadEndFilling => Box::new(DeadEndFilling::new()),
+    }
+}
+
+fn create_generator(ty: GeneratorType, seed: u64) -> Box<dyn MazeGenerator> {
+    match ty {
+        GeneratorType::RecursiveBacktracker => Box::new(RecursiveBacktracker::new(seed)),
+        GeneratorType::RecursiveDivision => Box::new(RecursiveDivision::new(None, seed)),
+        GeneratorType::Dungeon => {
+            let settings = DivisionSettings {
+                min_room_size: 4,
+                doorways: 2,
+                room_chance: 0.15,
+            };
+            Box::new(RecursiveDivision::with_settings(None, settings, seed))
+        }
+        GeneratorType::BinaryTree { bias: (dir_1, dir_2) } => {
+            //  The visualizer only ever offers the four diagonal biases, which are always valid
+            Box::new(BinaryTree::new(dir_1, dir_2, seed).expect("diagonal bias"))
+        }
+        GeneratorType::Prim => Box::new(Prim::new(seed)),
+        GeneratorType::Kruskal => Box::new(Kruskal::new(seed)),
+        GeneratorType::Wilson => Box::new(Wilson::new(seed)),
+        GeneratorType::AldousBroder => Box::new(AldousBroder::new(seed)),
+        GeneratorType::Eller => Box::new(Eller::new(seed)),
+        GeneratorType::Sidewinder => Box::new(Sidewinder::new(gen::Dir::North, seed)),
+        GeneratorType::HuntAndKill => Box::new(HuntAndKill::new(seed)),
+        GeneratorType::GrowingTree { newest_percent } => {
+            let newest_weight = newest_percent as f64 / 100.0;
+            let selection = CellSelection::Mix(vec![
+                (CellSelection::Newest, newest_weight),
+                (CellSelection::Random, 1.0 - newest_weight),
+            ]);
+            Box::new(GrowingTree::new(selection, seed))
+        }
+        GeneratorType::CellularAutomaton { rule } => {
+            let rule = rule.parse().expect("valid rule");
+            Box::new(CellularAutomaton::new(rule, 0.5, 200, seed))
+        }
+        GeneratorType::OriginShift => Box::new(OriginShift::new(1, seed)),
     }
 }
 
+const DIGIT_KEYS: [Key; 10] = [
+    Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4,
+    Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+];
+
 fn main() {
     let mut framebuffer = Framebuffer {
         buffer: vec![0; WIDTH * HEIGHT],
@@ -52,17 +125,60 @@ fn main() {
 
     let mut viz_desc = MazeVizDescritptor::new(50,50,WIDTH-100, HEIGHT-100, maze.width, maze.height);
 
+    //  The seed can be passed as the first argument to reproduce a maze, otherwise a random one is picked.
+    let mut seed = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse::<u64>().ok())
+        .unwrap_or_else(rand::random::<u64>);
+
+    //  While Some, the user is typing a new seed and digit keys are captured into it.
+    let mut seed_input: Option<String> = None;
+
     let mut current_type = GeneratorType::RecursiveDivision;
-    let mut generator = create_generator(current_type);
+    let mut generator = create_generator(current_type, seed);
     generator.initialize(&mut maze);
 
+    //  If enabled, half of the dead ends are braided away once generation finishes
+    let mut braid_enabled = false;
+    let mut braided = false;
+
+    //  Once generation finishes, a start and goal are placed on the border at the ends of the longest path,
+    //  and the maze is solved between them. Mazes without a path between two border cells are left unsolved.
+    let mut endpoints: Option<(usize, usize)> = None;
+    let mut endpoints_placed = false;
+    let mut solver: Option<Box<dyn MazeSolver>> = None;
+    let mut current_solver_type = SolverType::BreadthFirst;
+
+    //  If enabled, the finished maze is drawn as a heatmap of the distance from the start
+    let mut heatmap_enabled = false;
+    let mut distances: Option<DistanceMap> = None;
+
     let mut step_interval = 0.1;
     let mut last_time = std::time::SystemTime::now();
 
     while window.is_open() && !window.is_key_down(Key::Escape) {
         let mut should_regen = false;
 
-        if window.is_key_pressed(Key::Key1, minifb::KeyRepeat::No) {
+        if let Some(input) = seed_input.as_mut() {
+            //  Seed entry mode: digits append, backspace deletes and enter applies the seed.
+            for (digit, key) in DIGIT_KEYS.iter().enumerate() {
+                if window.is_key_pressed(*key, minifb::KeyRepeat::No) && input.len() < 20 {
+                    input.push_str(&digit.to_string());
+                }
+            }
+
+            if window.is_key_pressed(Key::Backspace, minifb::KeyRepeat::Yes) {
+                input.pop();
+            } else if window.is_key_pressed(Key::Enter, minifb::KeyRepeat::No) {
+                if let Ok(new_seed) = input.parse::<u64>() {
+                    seed = new_seed;
+                    should_regen = true;
+                }
+                seed_input = None;
+            }
+        } else if window.is_key_pressed(Key::S, minifb::KeyRepeat::No) {
+            seed_input = Some(String::new());
+        } else if window.is_key_pressed(Key::Key1, minifb::KeyRepeat::No) {
             should_regen = true;
             maze_width *= 2;
             maze_height *= 2;
@@ -72,14 +188,79 @@ fn main() {
             maze_height = 1.max(maze_height/2);
         } else if window.is_key_pressed(Key::R, minifb::KeyRepeat::No) {
             should_regen = true;
+            seed = rand::random::<u64>();
+        } else if window.is_key_pressed(Key::T, minifb::KeyRepeat::No) {
+            should_regen = true;
+        } else if window.is_key_pressed(Key::H, minifb::KeyRepeat::No) {
+            //  Switching solver restarts solving on the same maze
+            current_solver_type = match current_solver_type {
+                SolverType::BreadthFirst => SolverType::AStar(Heuristic::Manhattan),
+                SolverType::AStar(Heuristic::Manhattan) => SolverType::AStar(Heuristic::Euclidean),
+                SolverType::AStar(Heuristic::Euclidean) => SolverType::AStar(Heuristic::Zero),
+                SolverType::AStar(Heuristic::Zero) => SolverType::WallFollower(Hand::Left),
+                SolverType::WallFollower(Hand::Left) => SolverType::WallFollower(Hand::Right),
+                SolverType::WallFollower(Hand::Right) => SolverType::Tremaux,
+                SolverType::Tremaux => SolverType::DeadEndFilling,
+                SolverType::DeadEndFilling => SolverType::BreadthFirst,
+            };
+            solver = None;
+        } else if window.is_key_pressed(Key::I, minifb::KeyRepeat::No) {
+            //  Print the structure of the finished maze, for comparing generators
+            if let Some((start, goal)) = endpoints {
+                println!("{:?} ({}x{}, seed {})", current_type, maze.width, maze.height, seed);
+                println!("{}\n", analysis::analyze(&maze, start, goal));
+            }
+        } else if window.is_key_pressed(Key::G, minifb::KeyRepeat::No) {
+            heatmap_enabled = !heatmap_enabled;
+        } else if window.is_key_pressed(Key::K, minifb::KeyRepeat::No) {
+            should_regen = true;
+            braid_enabled = !braid_enabled;
         } else if window.is_key_pressed(Key::C, minifb::KeyRepeat::No) {
             current_type = match current_type {
                 GeneratorType::RecursiveBacktracker => GeneratorType::RecursiveDivision,
-                GeneratorType::RecursiveDivision => GeneratorType::BinaryTree,
-                GeneratorType::BinaryTree => GeneratorType::RecursiveBacktracker,
+                GeneratorType::RecursiveDivision => GeneratorType::Dungeon,
+                GeneratorType::Dungeon => GeneratorType::BinaryTree { bias: (gen::Dir::North, gen::Dir::East) },
+                GeneratorType::BinaryTree { .. } => GeneratorType::Prim,
+                GeneratorType::Prim => GeneratorType::Kruskal,
+                GeneratorType::Kruskal => GeneratorType::Wilson,
+                GeneratorType::Wilson => GeneratorType::AldousBroder,
+                GeneratorType::AldousBroder => GeneratorType::Eller,
+                GeneratorType::Eller => GeneratorType::Sidewinder,
+                GeneratorType::Sidewinder => GeneratorType::HuntAndKill,
+                GeneratorType::HuntAndKill => GeneratorType::GrowingTree { newest_percent: 75 },
+                GeneratorType::GrowingTree { .. } => GeneratorType::CellularAutomaton { rule: AUTOMATON_RULES[0] },
+                GeneratorType::CellularAutomaton { .. } => GeneratorType::OriginShift,
+                GeneratorType::OriginShift => GeneratorType::RecursiveBacktracker,
             };
 
             should_regen = true;
+        } else if let GeneratorType::GrowingTree { newest_percent } = &mut current_type {
+            //  N/M dials the growing tree between random (Prim-like) and newest (backtracker-like) selection
+            if window.is_key_pressed(Key::N, minifb::KeyRepeat::No) && *newest_percent > 0 {
+                *newest_percent -= 25;
+                should_regen = true;
+            } else if window.is_key_pressed(Key::M, minifb::KeyRepeat::No) && *newest_percent < 100 {
+                *newest_percent += 25;
+                should_regen = true;
+            }
+        } else if let GeneratorType::BinaryTree { bias } = &mut current_type {
+            //  B rotates the binary tree bias clockwise through the four diagonals
+            if window.is_key_pressed(Key::B, minifb::KeyRepeat::No) {
+                *bias = match *bias {
+                    (gen::Dir::North, gen::Dir::East) => (gen::Dir::South, gen::Dir::East),
+                    (gen::Dir::South, gen::Dir::East) => (gen::Dir::South, gen::Dir::West),
+                    (gen::Dir::South, gen::Dir::West) => (gen::Dir::North, gen::Dir::West),
+                    _ => (gen::Dir::North, gen::Dir::East),
+                };
+                should_regen = true;
+            }
+        } else if let GeneratorType::CellularAutomaton { rule } = &mut current_type {
+            //  V cycles the automaton rule
+            if window.is_key_pressed(Key::V, minifb::KeyRepeat::No) {
+                let rule_idx = AUTOMATON_RULES.iter().position(|r| r == rule).unwrap_or(0);
+                *rule = AUTOMATON_RULES[(rule_idx + 1) % AUTOMATON_RULES.len()];
+                should_regen = true;
+            }
         }
 
         if window.is_key_pressed(Key::P, minifb::KeyRepeat::No) {
@@ -92,29 +273,109 @@ fn main() {
             window.set_title("Regenerating...");
             
             maze = Maze::empty(maze_width, maze_height);
-            generator = create_generator(current_type);
+            generator = create_generator(current_type, seed);
             generator.initialize(&mut maze);
+            braided = false;
+            endpoints = None;
+            endpoints_placed = false;
+            solver = None;
+            distances = None;
 
             viz_desc.rescale(maze_width, maze_height);
         }
 
+        //  Endless generators keep moving the walls underneath the solver, which only a Trémaux walker copes with.
+        //  Wall followers would mistake a changed maze for a loop, and searches would draw stale paths.
+        let active_solver_type = if generator.is_endless() { SolverType::Tremaux } else { current_solver_type };
+
+        //  Searches also report how many cells they expanded, to compare them against each other
+        let solver_text = match solver.as_ref() {
+            Some(solver) => match solver.nodes_expanded() {
+                Some(expanded) => format!("{:?} ({:?}, {} expanded)", active_solver_type, solver.outcome(), expanded),
+                None => format!("{:?} ({:?})", active_solver_type, solver.outcome()),
+            },
+            None => format!("{:?}", active_solver_type),
+        };
+        let seed_text = match &seed_input {
+            Some(input) => format!("{}_ (Enter: apply)", input),
+            None => seed.to_string(),
+        };
         let title = format!(
-            "MazeGen. Controls: 1/2: Double/Half maze size. Curr: {}x{} R: Regen maze. P/O: (In)/(De)crease step freq. Curr: {}/s C: Cycle gen algo. Curr: {:?}",
-            maze.width, maze.height, 1.0/step_interval, current_type
+            "MazeGen. Controls: 1/2: Double/Half maze size. Curr: {}x{} R: Regen maze. T: Replay seed. S: Set seed. Curr: {} K: Toggle braiding. Curr: {} H: Cycle solver. Curr: {} G: Toggle heatmap. I: Print maze stats. P/O: (In)/(De)crease step freq. Curr: {}/s C: Cycle gen algo. Curr: {:?} (N/M: Tune growing tree. B: Rotate binary tree bias. V: Cycle automaton rule)",
+            maze.width, maze.height, seed_text, braid_enabled, solver_text, 1.0/step_interval, current_type
         );
         window.set_title(title.as_str());
 
         let now = std::time::SystemTime::now();
         let dur = now.duration_since(last_time).unwrap().as_secs_f64();
         if dur > step_interval {
-            generator.step(&mut maze);
+            //  Endless generators keep stepping alongside the solver, so the walls move while it walks
+            if solver.is_none() || generator.is_endless() {
+                generator.step(&mut maze);
+
+                //  Mazes are only perfect once finished, and not even then for every generator,
+                //  but walls must agree between neighbors after every step
+                if cfg!(debug_assertions) {
+                    let report = maze.validate();
+                    assert!(report.is_symmetric(), "{:?} left asymmetric walls: {}", current_type, report);
+                }
+            }
+
+            if let Some(solver) = solver.as_mut() {
+                solver.step(&maze);
+            }
             last_time += std::time::Duration::from_secs_f64(dur);
         }
 
+        if braid_enabled && !braided && generator.is_finished() {
+            gen::braid(&mut maze, 0.5, seed);
+            braided = true;
+        }
+
+        //  The maze of an endless generator is complete right after initialization
+        let maze_complete = generator.is_finished() || generator.is_endless();
+        if !endpoints_placed && maze_complete {
+            endpoints = solver::place_start_and_goal(&mut maze, true);
+            endpoints_placed = true;
+        }
+
+        //  Trémaux's marks go stale as the walls of a living maze move, so a walker that gave up starts over
+        if generator.is_endless() && solver.as_ref().is_some_and(|solver| solver.outcome() == WalkOutcome::GaveUp) {
+            solver = None;
+        }
+
+        if let (None, Some((start, goal))) = (&solver, endpoints) {
+            let mut new_solver = create_solver(active_solver_type, seed);
+            new_solver.initialize(&maze, start, goal);
+            solver = Some(new_solver);
+        }
+
+        //  Endless generators keep changing the maze, so their distances go stale every step
+        if heatmap_enabled && maze_complete && (distances.is_none() || generator.is_endless()) {
+            distances = Some(DistanceMap::new(&maze, endpoints.map_or(0, |(start, _)| start)));
+        }
+
         framebuffer.clear(0x0);
-        framebuffer.draw_maze(&maze, &viz_desc);
+        match distances.as_ref().filter(|_| heatmap_enabled) {
+            Some(distances) => framebuffer.draw_maze_heatmap(&maze, distances, &viz_desc),
+            None => framebuffer.draw_maze_with(&maze, &viz_desc, |idx| {
+                match endpoints {
+                    Some((start, _)) if idx == start => return Some(viz::START_COLOR),
+                    Some((_, goal)) if idx == goal => return Some(viz::GOAL_COLOR),
+                    _ => {}
+                }
+
+                solver
+                    .as_ref()
+                    .and_then(|solver| solver.highlight(idx).map(viz::solver_highlight_color))
+                    .or_else(|| generator.highlight(idx).map(viz::highlight_color))
+            }),
+        }
+        if let Some(path) = solver.as_ref().and_then(|solver| solver.path()) {
+            framebuffer.draw_path(&maze, path, viz::PATH_COLOR, &viz_desc);
+        }
         window
-            .update_with_buffer(&framebuffer.buffer.as_slice(), framebuffer.width, framebuffer.height)
+            .update_with_buffer(framebuffer.buffer.as_slice(), framebuffer.width, framebuffer.height)
             .unwrap();
     }
 }
\ No newline at end of file
@@ -0,0 +1,930 @@
+//  Copyright (c) Klas Henriksson 2022.
+//  All rights reserved.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::gen::{get_boundary_dirs, get_neighbor, get_open_neighbors, open_boundary, to_x_y, Dir, Maze};
+
+/// Solver specific state of a cell that a visualizer may want to show while solving is ongoing.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SolverHighlight {
+    /// The cell has been discovered, but not yet expanded.
+    Frontier,
+    /// The cell has been expanded.
+    Explored,
+    /// A walking solver is currently in the cell.
+    Walker,
+    /// A walking solver has passed through the cell.
+    Trail,
+    /// The cell has been ruled out from being part of a solution.
+    Filled,
+}
+
+pub trait MazeSolver {
+    /// Prepares the solver for finding a path from *start* to *goal* in *maze*.
+    fn initialize(&mut self, maze: &Maze, start: usize, goal: usize);
+    /// Perform a step of the solver.
+    fn step(&mut self, maze: &Maze);
+    /// Returns true if the solver is done, whether a path was found or not. Otherwise false.
+    fn is_finished(&self) -> bool;
+    /// Returns the path found, as cell indices from start to goal. None if no path has been found.
+    fn path(&self) -> Option<&[usize]>;
+
+    /// Returns the number of cells expanded so far, for solvers that search by expanding cells. None for others.
+    fn nodes_expanded(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns whether the solver is still going, reached the goal, gave up or looped. By default this follows from
+    /// [`MazeSolver::is_finished`] and [`MazeSolver::path`], solvers that can loop report it themselves.
+    fn outcome(&self) -> WalkOutcome {
+        if !self.is_finished() {
+            WalkOutcome::Walking
+        } else if self.path().is_some() {
+            WalkOutcome::ReachedGoal
+        } else {
+            WalkOutcome::GaveUp
+        }
+    }
+
+    /// Returns how the cell with index *cell_idx* should be highlighted, if at all.
+    fn highlight(&self, _cell_idx: usize) -> Option<SolverHighlight> {
+        None
+    }
+}
+
+/// Steps *solver* from *start* to *goal* until finished, and returns the path found if any.
+pub fn solve(solver: &mut dyn MazeSolver, maze: &Maze, start: usize, goal: usize) -> Option<Vec<usize>> {
+    solver.initialize(maze, start, goal);
+    while !solver.is_finished() {
+        solver.step(maze);
+    }
+    solver.path().map(|path| path.to_vec())
+}
+
+/// Distance from one cell to every other cell through the open passages of a maze.
+pub struct DistanceMap {
+    from: usize,
+    distances: Vec<Option<usize>>,
+    farthest: usize,
+}
+
+impl DistanceMap {
+    /// Computes the distance from the cell with index *from* to every cell in *maze*. Every passage has the
+    /// same length, so this is Dijkstra's algorithm reduced to a breadth-first search.
+    pub fn new(maze: &Maze, from: usize) -> Self {
+        let mut distances = vec![None; maze.cells.len()];
+        let mut queue = VecDeque::from([from]);
+        let mut farthest = from;
+        distances[from] = Some(0);
+
+        while let Some(cell_idx) = queue.pop_front() {
+            //  Cells leave the queue in order of distance, so the last one is the farthest
+            farthest = cell_idx;
+            let dist = distances[cell_idx].unwrap();
+
+            for nbor_idx in get_open_neighbors(cell_idx, maze) {
+                if distances[nbor_idx].is_none() {
+                    distances[nbor_idx] = Some(dist + 1);
+                    queue.push_back(nbor_idx);
+                }
+            }
+        }
+
+        Self {
+            from,
+            distances,
+            farthest,
+        }
+    }
+
+    /// Returns the cell the distances are measured from.
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Returns the distance to the cell with index *cell_idx*, or None if it cant be reached.
+    pub fn distance(&self, cell_idx: usize) -> Option<usize> {
+        self.distances[cell_idx]
+    }
+
+    /// Returns the distance to every cell, indexed by cell index. None for cells that cant be reached.
+    pub fn distances(&self) -> &[Option<usize>] {
+        &self.distances
+    }
+
+    /// Returns the reachable cell farthest away.
+    pub fn farthest(&self) -> usize {
+        self.farthest
+    }
+
+    /// Returns the distance to the reachable cell farthest away.
+    pub fn max_distance(&self) -> usize {
+        self.distances[self.farthest].unwrap()
+    }
+}
+
+/// Returns two cells at the ends of the longest path in *maze*, found by a double breadth-first search: the cell
+/// farthest from an arbitrary cell, and the cell farthest from that one. This is exact for perfect mazes. If
+/// *border_only*, only cells on the outer boundary are considered, which makes them suitable as entrance and exit.
+/// In a maze that is not connected, both cells are picked from the largest region with at least two candidates,
+/// or the largest region with any candidate at all, in which case the two cells may be the same.
+pub fn longest_path_endpoints(maze: &Maze, border_only: bool) -> (usize, usize) {
+    let is_candidate = |cell_idx: usize| !border_only || !get_boundary_dirs(cell_idx, maze.width, maze.height).is_empty();
+    let farthest_candidate = |distances: &DistanceMap| {
+        (0..maze.cells.len())
+            .filter(|&idx| is_candidate(idx))
+            .filter_map(|idx| distances.distance(idx).map(|distance| (distance, idx)))
+            .max_by_key(|&(distance, idx)| (distance, std::cmp::Reverse(idx)))
+            .map(|(_, idx)| idx)
+            .unwrap_or(distances.from())
+    };
+
+    let start = farthest_candidate(&DistanceMap::new(maze, largest_region_candidate(maze, is_candidate)));
+    let goal = farthest_candidate(&DistanceMap::new(maze, start));
+    (start, goal)
+}
+
+/// Returns the first candidate cell of the largest region of connected cells in *maze*, preferring regions with
+/// at least two candidates. Returns 0 if no cell is a candidate.
+fn largest_region_candidate(maze: &Maze, is_candidate: impl Fn(usize) -> bool) -> usize {
+    let mut in_region = vec![false; maze.cells.len()];
+    let mut best: Option<((bool, usize), usize)> = None;
+
+    for idx in 0..maze.cells.len() {
+        if in_region[idx] {
+            continue;
+        }
+
+        //  Flood the region, counting its cells and candidates
+        let mut first_candidate = None;
+        let (mut cells, mut candidates) = (0, 0);
+        let mut stack = vec![idx];
+        in_region[idx] = true;
+        while let Some(cell_idx) = stack.pop() {
+            cells += 1;
+            if is_candidate(cell_idx) {
+                candidates += 1;
+                first_candidate = Some(first_candidate.unwrap_or(cell_idx).min(cell_idx));
+            }
+
+            for nbor_idx in get_open_neighbors(cell_idx, maze) {
+                if !in_region[nbor_idx] {
+                    in_region[nbor_idx] = true;
+                    stack.push(nbor_idx);
+                }
+            }
+        }
+
+        if let Some(first_candidate) = first_candidate {
+            let rank = (candidates >= 2, cells);
+            if best.is_none_or(|(best_rank, _)| rank > best_rank) {
+                best = Some((rank, first_candidate));
+            }
+        }
+    }
+
+    best.map_or(0, |(_, first_candidate)| first_candidate)
+}
+
+/// Places a start and goal at the ends of the longest path in *maze*, see [`longest_path_endpoints`], and
+/// opens up the outer boundary walls at them, if they are on the boundary. Returns (start, goal), or None
+/// without touching the maze if there is no path between two different cells to place them at.
+pub fn place_start_and_goal(maze: &mut Maze, border_only: bool) -> Option<(usize, usize)> {
+    let (start, goal) = longest_path_endpoints(maze, border_only);
+    if start == goal {
+        return None;
+    }
+
+    open_boundary(maze, start);
+    open_boundary(maze, goal);
+    Some((start, goal))
+}
+
+/// Returns the path from *start* to *goal* by following *came_from* backwards from the goal.
+fn reconstruct_path(came_from: &[Option<usize>], start: usize, goal: usize) -> Vec<usize> {
+    let mut path = vec![goal];
+    let mut curr = goal;
+    while curr != start {
+        curr = came_from[curr].unwrap();
+        path.push(curr);
+    }
+    path.reverse();
+    path
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum SearchState {
+    Unseen,
+    Frontier,
+    Explored,
+}
+
+/// Breadth-first search. Expands cells in order of distance from the start, so the path found is a shortest one.
+pub struct BreadthFirst {
+    queue: VecDeque<usize>,
+    came_from: Vec<Option<usize>>,
+    state: Vec<SearchState>,
+    start: usize,
+    goal: usize,
+    path: Option<Vec<usize>>,
+    nodes_expanded: usize,
+    finished: bool,
+}
+
+impl BreadthFirst {
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            came_from: vec![],
+            state: vec![],
+            start: 0,
+            goal: 0,
+            path: None,
+            nodes_expanded: 0,
+            finished: false,
+        }
+    }
+}
+
+impl Default for BreadthFirst {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MazeSolver for BreadthFirst {
+    fn initialize(&mut self, maze: &Maze, start: usize, goal: usize) {
+        self.queue.clear();
+        self.came_from = vec![None; maze.cells.len()];
+        self.state = vec![SearchState::Unseen; maze.cells.len()];
+        self.start = start;
+        self.goal = goal;
+        self.path = None;
+        self.nodes_expanded = 0;
+        self.finished = false;
+
+        self.state[start] = SearchState::Frontier;
+        self.queue.push_back(start);
+    }
+
+    fn step(&mut self, maze: &Maze) {
+        if self.finished {
+            return;
+        }
+
+        //  Expand the oldest cell in the queue. If there is none, the goal cant be reached.
+        let cell_idx = match self.queue.pop_front() {
+            Some(cell_idx) => cell_idx,
+            None => {
+                self.finished = true;
+                return;
+            }
+        };
+        self.state[cell_idx] = SearchState::Explored;
+        self.nodes_expanded += 1;
+
+        if cell_idx == self.goal {
+            self.path = Some(reconstruct_path(&self.came_from, self.start, self.goal));
+            self.finished = true;
+            return;
+        }
+
+        for nbor_idx in get_open_neighbors(cell_idx, maze) {
+            if self.state[nbor_idx] == SearchState::Unseen {
+                self.state[nbor_idx] = SearchState::Frontier;
+                self.came_from[nbor_idx] = Some(cell_idx);
+                self.queue.push_back(nbor_idx);
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn path(&self) -> Option<&[usize]> {
+        self.path.as_deref()
+    }
+
+    fn nodes_expanded(&self) -> Option<usize> {
+        Some(self.nodes_expanded)
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<SolverHighlight> {
+        match self.state.get(cell_idx) {
+            Some(SearchState::Frontier) => Some(SolverHighlight::Frontier),
+            Some(SearchState::Explored) => Some(SolverHighlight::Explored),
+            _ => None,
+        }
+    }
+}
+
+/// Estimate of the remaining distance from a cell to the goal, used by [`AStar`].
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Heuristic {
+    /// Sum of the horizontal and vertical distance. The exact distance if there were no walls.
+    Manhattan,
+    /// Straight line distance.
+    Euclidean,
+    /// Always zero, which makes A* expand cells like a breadth-first search.
+    Zero,
+}
+
+impl Heuristic {
+    /// Returns the estimated distance between the cells with index *from* and *to* in a maze *width* cells wide.
+    pub fn estimate(&self, from: usize, to: usize, width: usize) -> f64 {
+        let (from_x, from_y) = to_x_y(from, width);
+        let (to_x, to_y) = to_x_y(to, width);
+        let dx = from_x.abs_diff(to_x) as f64;
+        let dy = from_y.abs_diff(to_y) as f64;
+
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Zero => 0.0,
+        }
+    }
+}
+
+/// Entry in the A* open set. Ordered so that a max-heap pops the lowest estimated total cost first,
+/// breaking ties in favor of the cell estimated closest to the goal.
+struct OpenEntry {
+    cost: f64,
+    estimate: f64,
+    cell_idx: usize,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| other.estimate.total_cmp(&self.estimate))
+    }
+}
+
+/// A* search. Expands the cell with the lowest distance from the start plus estimated distance to the goal.
+/// The path found is a shortest one, since none of the heuristics ever overestimate the distance.
+pub struct AStar {
+    heuristic: Heuristic,
+    open: BinaryHeap<OpenEntry>,
+    came_from: Vec<Option<usize>>,
+    /// Distance from the start along the best path found so far.
+    dist: Vec<usize>,
+    state: Vec<SearchState>,
+    start: usize,
+    goal: usize,
+    width: usize,
+    path: Option<Vec<usize>>,
+    nodes_expanded: usize,
+    finished: bool,
+}
+
+impl AStar {
+    pub fn new(heuristic: Heuristic) -> Self {
+        Self {
+            heuristic,
+            open: BinaryHeap::new(),
+            came_from: vec![],
+            dist: vec![],
+            state: vec![],
+            start: 0,
+            goal: 0,
+            width: 0,
+            path: None,
+            nodes_expanded: 0,
+            finished: false,
+        }
+    }
+
+    fn push_open(&mut self, cell_idx: usize) {
+        let estimate = self.heuristic.estimate(cell_idx, self.goal, self.width);
+        self.open.push(OpenEntry {
+            cost: self.dist[cell_idx] as f64 + estimate,
+            estimate,
+            cell_idx,
+        });
+    }
+}
+
+impl MazeSolver for AStar {
+    fn initialize(&mut self, maze: &Maze, start: usize, goal: usize) {
+        self.open.clear();
+        self.came_from = vec![None; maze.cells.len()];
+        self.dist = vec![usize::MAX; maze.cells.len()];
+        self.state = vec![SearchState::Unseen; maze.cells.len()];
+        self.start = start;
+        self.goal = goal;
+        self.width = maze.width;
+        self.path = None;
+        self.nodes_expanded = 0;
+        self.finished = false;
+
+        self.dist[start] = 0;
+        self.state[start] = SearchState::Frontier;
+        self.push_open(start);
+    }
+
+    fn step(&mut self, maze: &Maze) {
+        if self.finished {
+            return;
+        }
+
+        //  Expand the most promising cell. Cells may be in the open set several times if a shorter path
+        //  to them was found later, so skip the stale entries. If the open set runs dry, the goal cant be reached.
+        let cell_idx = loop {
+            match self.open.pop() {
+                Some(entry) if self.state[entry.cell_idx] == SearchState::Explored => continue,
+                Some(entry) => break entry.cell_idx,
+                None => {
+                    self.finished = true;
+                    return;
+                }
+            }
+        };
+        self.state[cell_idx] = SearchState::Explored;
+        self.nodes_expanded += 1;
+
+        if cell_idx == self.goal {
+            self.path = Some(reconstruct_path(&self.came_from, self.start, self.goal));
+            self.finished = true;
+            return;
+        }
+
+        for nbor_idx in get_open_neighbors(cell_idx, maze) {
+            let dist = self.dist[cell_idx] + 1;
+            if self.state[nbor_idx] != SearchState::Explored && dist < self.dist[nbor_idx] {
+                self.dist[nbor_idx] = dist;
+                self.came_from[nbor_idx] = Some(cell_idx);
+                self.state[nbor_idx] = SearchState::Frontier;
+                self.push_open(nbor_idx);
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn path(&self) -> Option<&[usize]> {
+        self.path.as_deref()
+    }
+
+    fn nodes_expanded(&self) -> Option<usize> {
+        Some(self.nodes_expanded)
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<SolverHighlight> {
+        match self.state.get(cell_idx) {
+            Some(SearchState::Frontier) => Some(SolverHighlight::Frontier),
+            Some(SearchState::Explored) => Some(SolverHighlight::Explored),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the cell reached by moving from *cell_idx* in direction *dir*, if there is no wall in the way.
+fn open_neighbor(cell_idx: usize, dir: Dir, maze: &Maze) -> Option<usize> {
+    if maze.cells[cell_idx].has_wall(dir) {
+        return None;
+    }
+    get_neighbor(cell_idx, dir, maze.width, maze.height)
+}
+
+/// Returns *trail* with every loop cut out, i.e. a path visiting each cell at most once.
+fn erase_loops(trail: &[usize]) -> Vec<usize> {
+    let mut path: Vec<usize> = vec![];
+    for &cell_idx in trail {
+        match path.iter().position(|&idx| idx == cell_idx) {
+            Some(pos) => path.truncate(pos + 1),
+            None => path.push(cell_idx),
+        }
+    }
+    path
+}
+
+/// Current state of a solver, see [`MazeSolver::outcome`]. Only solvers walking through the maze one cell at a time
+/// can end up looping.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum WalkOutcome {
+    /// The solver is still on its way.
+    Walking,
+    /// The solver reached the goal.
+    ReachedGoal,
+    /// The solver ran out of options without reaching the goal.
+    GaveUp,
+    /// The walker came back to a state it has already been in, and would walk in circles forever.
+    Looped,
+}
+
+/// The hand a [`WallFollower`] keeps on the wall.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Wall follower. Walks through the maze keeping one hand on the wall, moving one cell per step.
+/// Always finds the goal in a perfect maze, but may circle forever around an island in a braided one.
+pub struct WallFollower {
+    hand: Hand,
+    curr_idx: usize,
+    facing: Dir,
+    goal: usize,
+    trail: Vec<usize>,
+    on_trail: Vec<bool>,
+    /// Which directions the walker has been facing when leaving each cell, to detect loops.
+    seen: Vec<[bool; 4]>,
+    path: Option<Vec<usize>>,
+    outcome: WalkOutcome,
+}
+
+impl WallFollower {
+    pub fn new(hand: Hand) -> Self {
+        Self {
+            hand,
+            curr_idx: 0,
+            facing: Dir::East,
+            goal: 0,
+            trail: vec![],
+            on_trail: vec![],
+            seen: vec![],
+            path: None,
+            outcome: WalkOutcome::Walking,
+        }
+    }
+
+    /// Returns the direction the walker is facing.
+    pub fn facing(&self) -> Dir {
+        self.facing
+    }
+
+    /// Returns the cells walked through so far, in order, including revisits.
+    pub fn trail(&self) -> &[usize] {
+        &self.trail
+    }
+}
+
+impl MazeSolver for WallFollower {
+    fn initialize(&mut self, maze: &Maze, start: usize, goal: usize) {
+        self.curr_idx = start;
+        self.facing = Dir::East;
+        self.goal = goal;
+        self.trail = vec![start];
+        self.on_trail = vec![false; maze.cells.len()];
+        self.on_trail[start] = true;
+        self.seen = vec![[false; 4]; maze.cells.len()];
+        self.path = None;
+        self.outcome = WalkOutcome::Walking;
+
+        if start == goal {
+            self.path = Some(vec![start]);
+            self.outcome = WalkOutcome::ReachedGoal;
+        }
+    }
+
+    fn step(&mut self, maze: &Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        //  Prefer turning towards the hand on the wall, then straight ahead, then away from it, then back
+        let towards_hand = match self.hand {
+            Hand::Left => self.facing.turn_left(),
+            Hand::Right => self.facing.turn_right(),
+        };
+        let choices = [towards_hand, self.facing, towards_hand.opposite(), self.facing.opposite()];
+
+        let next = choices
+            .iter()
+            .find_map(|&dir| open_neighbor(self.curr_idx, dir, maze).map(|nbor_idx| (dir, nbor_idx)));
+        let (dir, nbor_idx) = match next {
+            Some(next) => next,
+            None => {
+                //  Walled in from all sides
+                self.outcome = WalkOutcome::GaveUp;
+                return;
+            }
+        };
+
+        //  Leaving the same cell in the same direction twice means the walk repeats from here on
+        let seen = &mut self.seen[self.curr_idx][dir as usize];
+        if *seen {
+            self.outcome = WalkOutcome::Looped;
+            return;
+        }
+        *seen = true;
+
+        self.facing = dir;
+        self.curr_idx = nbor_idx;
+        self.trail.push(nbor_idx);
+        self.on_trail[nbor_idx] = true;
+
+        if nbor_idx == self.goal {
+            self.path = Some(erase_loops(&self.trail));
+            self.outcome = WalkOutcome::ReachedGoal;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.outcome != WalkOutcome::Walking
+    }
+
+    fn path(&self) -> Option<&[usize]> {
+        self.path.as_deref()
+    }
+
+    fn outcome(&self) -> WalkOutcome {
+        self.outcome
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<SolverHighlight> {
+        if !self.on_trail.is_empty() && cell_idx == self.curr_idx {
+            Some(SolverHighlight::Walker)
+        } else if self.on_trail.get(cell_idx).copied().unwrap_or(false) {
+            Some(SolverHighlight::Trail)
+        } else {
+            None
+        }
+    }
+}
+
+/// Trémaux's algorithm. Walks through the maze one cell per step, marking every passage each time it is
+/// walked through. Passages are never walked more than twice, so it finds the goal in any maze, or gives up
+/// once every passage has been walked twice.
+pub struct Tremaux {
+    curr_idx: usize,
+    facing: Dir,
+    /// The direction back through the passage last walked, None at the start.
+    came_from: Option<Dir>,
+    goal: usize,
+    /// Number of times each passage has been walked, indexed by cell and direction. Both ends are counted.
+    marks: Vec<[u8; 4]>,
+    trail: Vec<usize>,
+    on_trail: Vec<bool>,
+    path: Option<Vec<usize>>,
+    outcome: WalkOutcome,
+    rng: StdRng,
+}
+
+impl Tremaux {
+    /// Construct a new Trémaux solver. Ties between passages are broken at random, the same *seed* and maze
+    /// always produce the same walk.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            curr_idx: 0,
+            facing: Dir::East,
+            came_from: None,
+            goal: 0,
+            marks: vec![],
+            trail: vec![],
+            on_trail: vec![],
+            path: None,
+            outcome: WalkOutcome::Walking,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the direction the walker is facing.
+    pub fn facing(&self) -> Dir {
+        self.facing
+    }
+
+    /// Returns the cells walked through so far, in order, including revisits.
+    pub fn trail(&self) -> &[usize] {
+        &self.trail
+    }
+
+    /// Picks a random passage among *dirs* leading out of the current cell.
+    fn pick(&mut self, dirs: &[Dir]) -> Dir {
+        dirs[self.rng.gen_range(0..dirs.len())]
+    }
+}
+
+impl MazeSolver for Tremaux {
+    fn initialize(&mut self, maze: &Maze, start: usize, goal: usize) {
+        self.curr_idx = start;
+        self.facing = Dir::East;
+        self.came_from = None;
+        self.goal = goal;
+        self.marks = vec![[0; 4]; maze.cells.len()];
+        self.trail = vec![start];
+        self.on_trail = vec![false; maze.cells.len()];
+        self.on_trail[start] = true;
+        self.path = None;
+        self.outcome = WalkOutcome::Walking;
+
+        if start == goal {
+            self.path = Some(vec![start]);
+            self.outcome = WalkOutcome::ReachedGoal;
+        }
+    }
+
+    fn step(&mut self, maze: &Maze) {
+        if self.is_finished() {
+            return;
+        }
+
+        let marks = self.marks[self.curr_idx];
+        let open: Vec<Dir> = [Dir::North, Dir::West, Dir::South, Dir::East]
+            .iter()
+            .copied()
+            .filter(|&dir| open_neighbor(self.curr_idx, dir, maze).is_some())
+            .collect();
+        let others: Vec<Dir> = open.iter().copied().filter(|&dir| Some(dir) != self.came_from).collect();
+        let unmarked_others: Vec<Dir> = others.iter().copied().filter(|&dir| marks[dir as usize] == 0).collect();
+
+        let dir = if !others.is_empty() && unmarked_others.len() == others.len() {
+            //  New junction, explore any of its passages
+            self.pick(&unmarked_others)
+        } else if let Some(back) = self.came_from.filter(|&back| marks[back as usize] == 1 && open.contains(&back)) {
+            //  Old junction entered through a new passage, or a dead end, so turn back. In a maze whose walls
+            //  move, the passage back may have been walled since.
+            back
+        } else {
+            //  Take the least walked passage, never one walked twice already
+            let fewest = open.iter().map(|&dir| marks[dir as usize]).filter(|&m| m < 2).min();
+            match fewest {
+                Some(fewest) => {
+                    let candidates: Vec<Dir> = open.iter().copied().filter(|&dir| marks[dir as usize] == fewest).collect();
+                    self.pick(&candidates)
+                }
+                None => {
+                    self.outcome = WalkOutcome::GaveUp;
+                    return;
+                }
+            }
+        };
+
+        let nbor_idx = open_neighbor(self.curr_idx, dir, maze).unwrap();
+        self.marks[self.curr_idx][dir as usize] += 1;
+        self.marks[nbor_idx][dir.opposite() as usize] += 1;
+
+        self.facing = dir;
+        self.came_from = Some(dir.opposite());
+        self.curr_idx = nbor_idx;
+        self.trail.push(nbor_idx);
+        self.on_trail[nbor_idx] = true;
+
+        if nbor_idx == self.goal {
+            self.path = Some(erase_loops(&self.trail));
+            self.outcome = WalkOutcome::ReachedGoal;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.outcome != WalkOutcome::Walking
+    }
+
+    fn path(&self) -> Option<&[usize]> {
+        self.path.as_deref()
+    }
+
+    fn outcome(&self) -> WalkOutcome {
+        self.outcome
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<SolverHighlight> {
+        if !self.on_trail.is_empty() && cell_idx == self.curr_idx {
+            Some(SolverHighlight::Walker)
+        } else if self.on_trail.get(cell_idx).copied().unwrap_or(false) {
+            Some(SolverHighlight::Trail)
+        } else {
+            None
+        }
+    }
+}
+
+/// Dead-end filling. Repeatedly fills in dead ends, i.e. cells with only a single open passage, other than the
+/// start and goal, one per step. Filling a dead end may turn its neighbor into a new one. What remains unfilled
+/// once no dead ends are left are the corridors of the solution, and in mazes with loops, the loops.
+pub struct DeadEndFilling {
+    /// Number of open passages to unfilled neighbors, per cell.
+    open_count: Vec<usize>,
+    filled: Vec<bool>,
+    dead_ends: Vec<usize>,
+    start: usize,
+    goal: usize,
+    path: Option<Vec<usize>>,
+    finished: bool,
+}
+
+impl DeadEndFilling {
+    pub fn new() -> Self {
+        Self {
+            open_count: vec![],
+            filled: vec![],
+            dead_ends: vec![],
+            start: 0,
+            goal: 0,
+            path: None,
+            finished: false,
+        }
+    }
+
+    /// Returns the cells that have not been filled, in index order. Once finished, this is the solution set.
+    pub fn solution_cells(&self) -> Vec<usize> {
+        (0..self.filled.len()).filter(|&idx| !self.filled[idx]).collect()
+    }
+
+    /// Returns true if *cell_idx* is a dead end that should be filled.
+    fn is_fillable(&self, cell_idx: usize) -> bool {
+        !self.filled[cell_idx] && self.open_count[cell_idx] <= 1 && cell_idx != self.start && cell_idx != self.goal
+    }
+
+    /// Finds the path from start to goal through the unfilled cells.
+    fn find_path(&self, maze: &Maze) -> Option<Vec<usize>> {
+        let mut came_from = vec![None; maze.cells.len()];
+        let mut discovered = vec![false; maze.cells.len()];
+        let mut queue = VecDeque::from([self.start]);
+        discovered[self.start] = true;
+
+        while let Some(cell_idx) = queue.pop_front() {
+            if cell_idx == self.goal {
+                return Some(reconstruct_path(&came_from, self.start, self.goal));
+            }
+
+            for nbor_idx in get_open_neighbors(cell_idx, maze) {
+                if !self.filled[nbor_idx] && !discovered[nbor_idx] {
+                    discovered[nbor_idx] = true;
+                    came_from[nbor_idx] = Some(cell_idx);
+                    queue.push_back(nbor_idx);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Default for DeadEndFilling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MazeSolver for DeadEndFilling {
+    fn initialize(&mut self, maze: &Maze, start: usize, goal: usize) {
+        self.open_count = (0..maze.cells.len()).map(|idx| get_open_neighbors(idx, maze).len()).collect();
+        self.filled = vec![false; maze.cells.len()];
+        self.start = start;
+        self.goal = goal;
+        self.path = None;
+        self.finished = false;
+        self.dead_ends = (0..maze.cells.len()).filter(|&idx| self.is_fillable(idx)).collect();
+    }
+
+    fn step(&mut self, maze: &Maze) {
+        if self.finished {
+            return;
+        }
+
+        //  Fill in one dead end. If there are none left, whatever is unfilled is the solution.
+        let cell_idx = match self.dead_ends.pop() {
+            Some(cell_idx) => cell_idx,
+            None => {
+                self.path = self.find_path(maze);
+                self.finished = true;
+                return;
+            }
+        };
+        self.filled[cell_idx] = true;
+
+        for nbor_idx in get_open_neighbors(cell_idx, maze) {
+            if self.filled[nbor_idx] {
+                continue;
+            }
+
+            self.open_count[nbor_idx] -= 1;
+            if self.is_fillable(nbor_idx) {
+                self.dead_ends.push(nbor_idx);
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn path(&self) -> Option<&[usize]> {
+        self.path.as_deref()
+    }
+
+    fn highlight(&self, cell_idx: usize) -> Option<SolverHighlight> {
+        self.filled.get(cell_idx).copied().unwrap_or(false).then_some(SolverHighlight::Filled)
+    }
+}
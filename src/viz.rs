@@ -1,9 +1,48 @@
 //  Copyright (c) Klas Henriksson 2022.
 //  All rights reserved.
 
-use crate::gen::{Maze, self, Dir};
+use crate::gen::{Maze, self, Dir, CellHighlight};
+use crate::solver::{DistanceMap, SolverHighlight};
 
 const WALL_COLOR: u32 = 0xffffffff;
+pub const PATH_COLOR: u32 = 0xffffff;
+pub const START_COLOR: u32 = 0x00ff00;
+pub const GOAL_COLOR: u32 = 0x00ffff;
+const UNREACHABLE_COLOR: u32 = 0x202020;
+
+/// Returns the color used to draw a cell with the given highlight.
+pub fn highlight_color(highlight: CellHighlight) -> u32 {
+    match highlight {
+        //  Scatter group ids over the color space, keeping every channel bright enough to tell apart from walls.
+        CellHighlight::Group(id) => ((id as u32).wrapping_mul(0x9e3779b1) >> 8) | 0x303030,
+        CellHighlight::Active => 0xffd700,
+    }
+}
+
+/// Returns the color at *t* in the range [0, 1] along a blue, green, yellow, red gradient.
+pub fn gradient_color(t: f32) -> u32 {
+    const STOPS: [(f32, f32, f32); 4] = [(0.0, 0.0, 255.0), (0.0, 255.0, 0.0), (255.0, 255.0, 0.0), (255.0, 0.0, 0.0)];
+
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let i = (t as usize).min(STOPS.len() - 2);
+    let f = t - i as f32;
+    let (r_0, g_0, b_0) = STOPS[i];
+    let (r_1, g_1, b_1) = STOPS[i + 1];
+
+    let lerp = |a: f32, b: f32| (a + (b - a) * f) as u32;
+    (lerp(r_0, r_1) << 16) | (lerp(g_0, g_1) << 8) | lerp(b_0, b_1)
+}
+
+/// Returns the color used to draw a cell with the given solver highlight.
+pub fn solver_highlight_color(highlight: SolverHighlight) -> u32 {
+    match highlight {
+        SolverHighlight::Frontier => 0x00c060,
+        SolverHighlight::Explored => 0x006030,
+        SolverHighlight::Walker => 0xff00ff,
+        SolverHighlight::Trail => 0x802080,
+        SolverHighlight::Filled => 0x404040,
+    }
+}
 
 pub struct MazeVizDescritptor {
     pub offset_x: usize,
@@ -24,8 +63,8 @@ impl MazeVizDescritptor {
             offset_y,
             width,
             height,
-            x_scale: x_scale.ceil() as usize,
-            y_scale: y_scale.ceil() as usize,
+            x_scale: 1.max(x_scale.floor() as usize),
+            y_scale: 1.max(y_scale.floor() as usize),
         }
     }
 
@@ -34,8 +73,13 @@ impl MazeVizDescritptor {
         let x_scale = self.width as f32 / maze_width as f32;
         let y_scale = self.height as f32 / maze_height as f32;
 
-        self.x_scale = x_scale.ceil() as usize;
-        self.y_scale = y_scale.ceil() as usize;
+        self.x_scale = 1.max(x_scale.floor() as usize);
+        self.y_scale = 1.max(y_scale.floor() as usize);
+    }
+
+    /// Returns the thickness of walls in pixels.
+    fn wall_size(&self) -> usize {
+        1.max(self.x_scale/10)
     }
 }
 
@@ -53,15 +97,35 @@ impl Framebuffer {
     }
 
     pub fn draw_maze(&mut self, maze: &Maze, desc: &MazeVizDescritptor) {
+        self.draw_maze_with(maze, desc, |_| None);
+    }
+
+    /// Draws the maze as a heatmap, coloring every cell by its distance in *distances* along a gradient
+    /// from the nearest to the farthest cell.
+    pub fn draw_maze_heatmap(&mut self, maze: &Maze, distances: &DistanceMap, desc: &MazeVizDescritptor) {
+        let max_distance = distances.max_distance().max(1) as f32;
+        self.draw_maze_with(maze, desc, |idx| {
+            Some(match distances.distance(idx) {
+                Some(distance) => gradient_color(distance as f32 / max_distance),
+                None => UNREACHABLE_COLOR,
+            })
+        });
+    }
+
+    /// Draws the maze like [`Framebuffer::draw_maze`], but colors each cell for which *cell_color*
+    /// returns a color with that color instead.
+    pub fn draw_maze_with<F: Fn(usize) -> Option<u32>>(&mut self, maze: &Maze, desc: &MazeVizDescritptor, cell_color: F) {
         for cell in maze.cells.iter() {
-            let color = if cell.visited {
+            let color = if let Some(color) = cell_color(cell.idx) {
+                color
+            } else if cell.visited {
                 0xff0000
             } else {
                 0xff
             };
 
             let (x,y) = gen::to_x_y(cell.idx, maze.width);
-            self.draw_cell(x,y,color,&desc);
+            self.draw_cell(x,y,color,desc);
         }
     
         for x in 0..maze.width {
@@ -69,28 +133,56 @@ impl Framebuffer {
                 let idx = gen::to_idx(x, y, maze.width);
                 let cell = &maze.cells[idx];
     
-                if cell.wall_east && x < maze.width - 1 {
+                if cell.wall_east {
                     self.draw_wall(x, y, Dir::East, WALL_COLOR, desc);
                 }
     
-                if cell.wall_west && x > 0 {
+                if cell.wall_west {
                     self.draw_wall(x, y, Dir::West, WALL_COLOR, desc);
                 }
     
-                if cell.wall_north && y > 0 {
+                if cell.wall_north {
                     self.draw_wall(x, y, Dir::North, WALL_COLOR, desc);
                 }
     
-                if cell.wall_south && y < maze.height - 1 {
+                if cell.wall_south {
                     self.draw_wall(x, y, Dir::South, WALL_COLOR, desc);
                 }
             }
         }
     }
 
+    /// Draws *path* as a line through the centers of its cells. Should be drawn after the maze.
+    pub fn draw_path(&mut self, maze: &Maze, path: &[usize], color: u32, desc: &MazeVizDescritptor) {
+        let (x_scale, y_scale) = (desc.x_scale, desc.y_scale);
+        let thickness = 1.max(x_scale.min(y_scale) / 5);
+        let center = |cell_idx: usize| {
+            let (x, y) = gen::to_x_y(cell_idx, maze.width);
+            (x * x_scale + x_scale.saturating_sub(thickness) / 2, y * y_scale + y_scale.saturating_sub(thickness) / 2)
+        };
+
+        //  A single cell path still gets a dot, other paths are drawn segment by segment
+        let segments: Vec<(usize, usize)> = if path.len() == 1 {
+            vec![(path[0], path[0])]
+        } else {
+            path.windows(2).map(|pair| (pair[0], pair[1])).collect()
+        };
+
+        for (from, to) in segments {
+            let (from_x, from_y) = center(from);
+            let (to_x, to_y) = center(to);
+
+            for x in from_x.min(to_x)..from_x.max(to_x) + thickness {
+                for y in from_y.min(to_y)..from_y.max(to_y) + thickness {
+                    self.draw(x + desc.offset_x, y + desc.offset_y, color, desc);
+                }
+            }
+        }
+    }
+
     fn draw_wall(&mut self, x: usize, y: usize, dir: Dir, color: u32, viz_desc: &MazeVizDescritptor) {
         let (x_scale, y_scale) = (viz_desc.x_scale, viz_desc.y_scale);
-        let wall_size = 1.max(x_scale/10);
+        let wall_size = viz_desc.wall_size();
     
         let (x_start, x_end, y_start, y_end) = match dir {
             Dir::North => (x*x_scale, x*x_scale+x_scale + wall_size, y*y_scale, y*y_scale + wall_size),
@@ -122,7 +214,12 @@ impl Framebuffer {
     }
 
     fn draw(&mut self, x: usize, y: usize, color: u32, viz_desc: &MazeVizDescritptor) {
-        if x >= viz_desc.offset_x + viz_desc.width || y >= viz_desc.offset_y + viz_desc.height {
+        //  Leave room for the outer walls along the far edges, as long as they fit in the framebuffer
+        let wall_size = viz_desc.wall_size();
+        if x >= viz_desc.offset_x + viz_desc.width + wall_size || y >= viz_desc.offset_y + viz_desc.height + wall_size {
+            return;
+        }
+        if x >= self.width || y >= self.height {
             return;
         }
         self.buffer[x + y * self.width] = color;
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{open_east, open_south};
+use mazegen::{analyze, Maze};
+
+#[test]
+fn corridor() {
+    let mut maze = Maze::empty(5, 1);
+    for idx in 0..4 {
+        open_east(&mut maze, idx);
+    }
+
+    let stats = analyze(&maze, 0, 4);
+    assert_eq!((stats.dead_ends, stats.corridors, stats.junctions, stats.crossroads, stats.isolated), (2, 3, 0, 0, 0));
+    assert_eq!(stats.average_corridor_length, 3.0);
+    //  Both dead-end branches run all the way to the other dead end
+    assert_eq!(stats.river_factor, 5.0);
+    assert_eq!(stats.solution_length, Some(5));
+    assert_eq!(stats.solution_ratio(), Some(1.0));
+    assert_eq!((stats.horizontal_passages, stats.vertical_passages), (4, 0));
+    assert_eq!(stats.horizontal_bias(), 1.0);
+    assert_eq!(stats.dead_end_directions, [0, 1, 0, 1]);
+}
+
+#[test]
+fn junction_with_branches() {
+    //  A corridor along the top row, with a branch running south from its middle
+    //  #####
+    //    #
+    //    #
+    let mut maze = Maze::empty(5, 3);
+    for idx in 0..4 {
+        open_east(&mut maze, idx);
+    }
+    open_south(&mut maze, 2);
+    open_south(&mut maze, 7);
+
+    let stats = analyze(&maze, 0, 12);
+    assert_eq!((stats.dead_ends, stats.corridors, stats.junctions, stats.crossroads, stats.isolated), (3, 3, 1, 0, 8));
+    assert_eq!(stats.average_corridor_length, 1.0);
+    assert_eq!(stats.river_factor, 2.0);
+    assert_eq!(stats.solution_length, Some(5));
+    assert_eq!(stats.solution_ratio(), Some(5.0 / 15.0));
+    assert_eq!((stats.horizontal_passages, stats.vertical_passages), (4, 2));
+    assert_eq!(stats.dead_end_directions, [1, 1, 0, 1]);
+}
+
+#[test]
+fn crossroad() {
+    //  A plus sign, leaving the corners walled off
+    let mut maze = Maze::empty(3, 3);
+    open_east(&mut maze, 3);
+    open_east(&mut maze, 4);
+    open_south(&mut maze, 1);
+    open_south(&mut maze, 4);
+
+    let stats = analyze(&maze, 1, 7);
+    assert_eq!((stats.dead_ends, stats.corridors, stats.junctions, stats.crossroads, stats.isolated), (4, 0, 0, 1, 4));
+    assert_eq!(stats.average_corridor_length, 0.0);
+    assert_eq!(stats.river_factor, 1.0);
+    assert_eq!(stats.solution_length, Some(3));
+    assert_eq!(stats.horizontal_bias(), 0.5);
+    assert_eq!(stats.dead_end_directions, [1, 1, 1, 1]);
+
+    assert_eq!(analyze(&maze, 0, 8).solution_length, None);
+    assert_eq!(analyze(&maze, 0, 8).solution_ratio(), None);
+}
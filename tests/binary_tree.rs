@@ -0,0 +1,16 @@
+use mazegen::{BinaryTree, BinaryTreeError, Dir};
+
+#[test]
+fn rejects_directions_that_are_not_diagonal() {
+    assert_eq!(BinaryTree::new(Dir::North, Dir::North, 1).err(), Some(BinaryTreeError::SameDirection(Dir::North)));
+    assert_eq!(BinaryTree::new(Dir::North, Dir::South, 1).err(), Some(BinaryTreeError::SameAxis(Dir::North, Dir::South)));
+    assert_eq!(BinaryTree::new(Dir::East, Dir::West, 1).err(), Some(BinaryTreeError::SameAxis(Dir::East, Dir::West)));
+}
+
+#[test]
+fn accepts_all_four_diagonals() {
+    for (dir_1, dir_2) in [(Dir::North, Dir::East), (Dir::South, Dir::East), (Dir::South, Dir::West), (Dir::North, Dir::West)] {
+        assert!(BinaryTree::new(dir_1, dir_2, 1).is_ok(), "{:?} {:?}", dir_1, dir_2);
+        assert!(BinaryTree::new(dir_2, dir_1, 1).is_ok(), "{:?} {:?}", dir_2, dir_1);
+    }
+}
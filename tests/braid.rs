@@ -0,0 +1,32 @@
+mod common;
+
+use common::generate;
+use mazegen::{braid, get_open_neighbors, Maze};
+
+fn count_dead_ends(maze: &Maze) -> usize {
+    (0..maze.cells.len()).filter(|&idx| get_open_neighbors(idx, maze).len() == 1).count()
+}
+
+#[test]
+fn removes_exactly_the_fraction_of_dead_ends() {
+    for seed in 0..200 {
+        for fraction in [0.25, 0.5, 0.75] {
+            let mut maze = generate(10, 10, seed);
+            let dead_ends = count_dead_ends(&maze);
+            let target = (dead_ends as f64 * fraction).round() as usize;
+
+            let removed = braid(&mut maze, fraction, seed);
+            assert_eq!(removed, target, "seed {} fraction {}", seed, fraction);
+            assert_eq!(count_dead_ends(&maze), dead_ends - target, "seed {} fraction {}", seed, fraction);
+        }
+    }
+}
+
+#[test]
+fn full_braid_leaves_no_dead_ends() {
+    let mut maze = generate(20, 20, 3);
+    braid(&mut maze, 1.0, 3);
+
+    assert_eq!(count_dead_ends(&maze), 0);
+    assert!(maze.validate().is_connected());
+}
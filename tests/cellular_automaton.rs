@@ -0,0 +1,25 @@
+use mazegen::LifeRule;
+
+#[test]
+fn rule_round_trips_through_display() {
+    for rule in [LifeRule::MAZE, LifeRule::CAVE] {
+        assert_eq!(rule.to_string().parse::<LifeRule>(), Ok(rule));
+    }
+
+    let life: LifeRule = "B3/S23".parse().unwrap();
+    assert_eq!(life.to_string(), "B3/S23");
+}
+
+#[test]
+fn parses_lowercase_and_survival_first() {
+    assert_eq!("b3/s12345".parse::<LifeRule>(), Ok(LifeRule::MAZE));
+    assert_eq!("S12345/B3".parse::<LifeRule>(), Ok(LifeRule::MAZE));
+    assert_eq!(" s345678/b678 ".parse::<LifeRule>(), Ok(LifeRule::CAVE));
+}
+
+#[test]
+fn rejects_invalid_rules() {
+    for rule in ["B9/S1", "B3/B3", "B3", "S23", "", "B3/S23/S1", "X3/S23", "B3/S2a"] {
+        assert!(rule.parse::<LifeRule>().is_err(), "{:?}", rule);
+    }
+}
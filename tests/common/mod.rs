@@ -0,0 +1,44 @@
+//! Fixtures shared by the integration tests. Every test crate compiles its own copy and uses only some of them.
+#![allow(dead_code)]
+
+use mazegen::{Maze, MazeGenerator, RecursiveBacktracker};
+
+/// Runs the generator to completion, calling *inspect* with the maze after initializing it and after every step.
+/// Returns the number of steps it took.
+pub fn run_with(generator: &mut dyn MazeGenerator, maze: &mut Maze, mut inspect: impl FnMut(&Maze)) -> usize {
+    generator.initialize(maze);
+    inspect(maze);
+
+    let mut steps = 0;
+    while !generator.is_finished() {
+        generator.step(maze);
+        inspect(maze);
+        steps += 1;
+    }
+    steps
+}
+
+/// Runs the generator to completion and returns the number of steps it took.
+pub fn run(generator: &mut dyn MazeGenerator, maze: &mut Maze) -> usize {
+    run_with(generator, maze, |_| {})
+}
+
+/// Generates a perfect maze with the recursive backtracker.
+pub fn generate(width: usize, height: usize, seed: u64) -> Maze {
+    let mut maze = Maze::empty(width, height);
+    run(&mut RecursiveBacktracker::new(seed), &mut maze);
+    maze
+}
+
+/// Opens the wall between the cell with index *idx* and its east neighbor.
+pub fn open_east(maze: &mut Maze, idx: usize) {
+    maze.cells[idx].wall_east = false;
+    maze.cells[idx + 1].wall_west = false;
+}
+
+/// Opens the wall between the cell with index *idx* and its south neighbor.
+pub fn open_south(maze: &mut Maze, idx: usize) {
+    let width = maze.width;
+    maze.cells[idx].wall_south = false;
+    maze.cells[idx + width].wall_north = false;
+}
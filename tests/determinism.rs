@@ -0,0 +1,67 @@
+use mazegen::{
+    braid, AldousBroder, BinaryTree, CellSelection, CellularAutomaton, Dir, DivisionSettings, Eller, GrowingTree,
+    HuntAndKill, Kruskal, LifeRule, Maze, MazeGenerator, OriginShift, Prim, RecursiveBacktracker, RecursiveDivision,
+    Sidewinder, Wilson,
+};
+
+/// Builds a generator from a seed.
+type NewGenerator = dyn Fn(u64) -> Box<dyn MazeGenerator>;
+
+/// Generates a maze with the generator built by *new_generator* from *seed*. Endless generators are stepped
+/// a fixed number of times instead of until finished.
+fn generate(new_generator: &NewGenerator, seed: u64) -> Maze {
+    let mut maze = Maze::empty(24, 16);
+    let mut generator = new_generator(seed);
+    generator.initialize(&mut maze);
+
+    let mut steps = 0;
+    while !generator.is_finished() && steps < 1000 {
+        generator.step(&mut maze);
+        steps += 1;
+    }
+    maze
+}
+
+#[test]
+fn same_seed_produces_same_maze() {
+    let generators: Vec<(&str, Box<NewGenerator>)> = vec![
+        ("recursive backtracker", Box::new(|seed| Box::new(RecursiveBacktracker::new(seed)))),
+        ("recursive division", Box::new(|seed| Box::new(RecursiveDivision::new(None, seed)))),
+        (
+            "dungeon",
+            Box::new(|seed| {
+                let settings = DivisionSettings { min_room_size: 4, doorways: 2, room_chance: 0.15 };
+                Box::new(RecursiveDivision::with_settings(None, settings, seed))
+            }),
+        ),
+        ("binary tree", Box::new(|seed| Box::new(BinaryTree::new(Dir::North, Dir::East, seed).unwrap()))),
+        ("prim", Box::new(|seed| Box::new(Prim::new(seed)))),
+        ("kruskal", Box::new(|seed| Box::new(Kruskal::new(seed)))),
+        ("wilson", Box::new(|seed| Box::new(Wilson::new(seed)))),
+        ("aldous broder", Box::new(|seed| Box::new(AldousBroder::new(seed)))),
+        ("eller", Box::new(|seed| Box::new(Eller::new(seed)))),
+        ("sidewinder", Box::new(|seed| Box::new(Sidewinder::new(Dir::North, seed)))),
+        ("hunt and kill", Box::new(|seed| Box::new(HuntAndKill::new(seed)))),
+        ("growing tree", Box::new(|seed| Box::new(GrowingTree::new(CellSelection::Random, seed)))),
+        ("cellular automaton", Box::new(|seed| Box::new(CellularAutomaton::new(LifeRule::MAZE, 0.5, 50, seed)))),
+        ("origin shift", Box::new(|seed| Box::new(OriginShift::new(3, seed)))),
+    ];
+
+    for (name, new_generator) in generators.iter() {
+        let maze = generate(new_generator.as_ref(), 1234);
+        assert_eq!(maze.cells, generate(new_generator.as_ref(), 1234).cells, "{}", name);
+        assert_ne!(maze.cells, generate(new_generator.as_ref(), 4321).cells, "{}", name);
+    }
+}
+
+#[test]
+fn same_seed_produces_same_braid() {
+    let braided = |seed: u64| {
+        let mut maze = generate(&|seed| Box::new(RecursiveBacktracker::new(seed)), 1234);
+        braid(&mut maze, 0.5, seed);
+        maze
+    };
+
+    assert_eq!(braided(7).cells, braided(7).cells);
+    assert_ne!(braided(7).cells, braided(8).cells);
+}
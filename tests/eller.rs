@@ -0,0 +1,43 @@
+mod common;
+
+use common::run;
+use mazegen::{Eller, EllerRows, Maze};
+
+/// Stitches *height* rows streamed from *rows* into a maze, closing it off with the last row.
+fn stitch(rows: &mut EllerRows, width: usize, height: usize) -> Maze {
+    let mut cells = Vec::with_capacity(width * height);
+    for _ in 0..height - 1 {
+        cells.extend(rows.next_row());
+    }
+    cells.extend(rows.last_row());
+
+    Maze { width, height, cells }
+}
+
+#[test]
+fn streamed_rows_form_a_perfect_maze() {
+    for (width, height) in [(1, 1), (1, 9), (9, 1), (17, 50)] {
+        let maze = stitch(&mut EllerRows::new(width, 7), width, height);
+
+        let report = maze.validate();
+        assert!(report.is_perfect(), "{}x{}: {}", width, height, report);
+    }
+}
+
+#[test]
+fn iterator_streams_the_same_rows() {
+    let mut rows = EllerRows::new(17, 7);
+    let streamed: Vec<_> = EllerRows::new(17, 7).take(20).collect();
+
+    for row in streamed {
+        assert_eq!(row, rows.next_row());
+    }
+}
+
+#[test]
+fn generator_matches_streamed_rows() {
+    let mut maze = Maze::empty(17, 50);
+    run(&mut Eller::new(7), &mut maze);
+
+    assert_eq!(maze.cells, stitch(&mut EllerRows::new(17, 7), 17, 50).cells);
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use common::run;
+use mazegen::{Maze, RecursiveBacktracker};
+
+#[test]
+fn large_maze_does_not_overflow_stack() {
+    let mut maze = Maze::empty(2000, 2000);
+    let steps = run(&mut RecursiveBacktracker::new(7), &mut maze);
+
+    assert!(maze.cells.iter().all(|cell| cell.visited));
+    //  One carve per step for every cell but the start cell, plus the final step that empties the stack.
+    assert_eq!(steps, maze.cells.len());
+}
+
+#[test]
+fn large_corridor_does_not_overflow_stack() {
+    //  A single row forces the whole maze to be one long corridor, so the final backtrack is as deep as
+    //  it can possibly get.
+    let mut maze = Maze::empty(2000 * 2000, 1);
+    run(&mut RecursiveBacktracker::new(7), &mut maze);
+
+    assert!(maze.cells.iter().all(|cell| cell.visited));
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::run;
+use mazegen::{DivisionSettings, Maze, RecursiveDivision};
+
+/// Returns the number of walls between horizontally adjacent cells, not counting the outer boundary.
+fn count_interior_east_walls(maze: &Maze) -> usize {
+    maze.cells.iter().filter(|cell| cell.wall_east && cell.idx % maze.width != maze.width - 1).count()
+}
+
+/// Returns the number of walls between vertically adjacent cells, not counting the outer boundary.
+fn count_interior_south_walls(maze: &Maze) -> usize {
+    maze.cells.iter().filter(|cell| cell.wall_south && cell.idx / maze.width != maze.height - 1).count()
+}
+
+#[test]
+fn single_doorways_produce_perfect_mazes() {
+    let settings = DivisionSettings { min_room_size: 1, doorways: 1, room_chance: 0.0 };
+    assert_eq!(settings, DivisionSettings::default());
+
+    for seed in 0..20 {
+        for (width, height) in [(1, 1), (1, 12), (12, 1), (2, 2), (23, 17)] {
+            let mut maze = Maze::empty(width, height);
+            run(&mut RecursiveDivision::with_settings(None, settings, seed), &mut maze);
+
+            let report = maze.validate();
+            assert!(report.is_perfect(), "seed {} {}x{}: {}", seed, width, height, report);
+        }
+    }
+}
+
+#[test]
+fn multiple_doorways_introduce_loops() {
+    let settings = DivisionSettings { doorways: 3, ..DivisionSettings::default() };
+
+    for seed in 0..20 {
+        let mut maze = Maze::empty(20, 20);
+        run(&mut RecursiveDivision::with_settings(None, settings, seed), &mut maze);
+
+        let report = maze.validate();
+        assert!(report.is_symmetric() && report.is_connected(), "seed {}: {}", seed, report);
+        assert!(!report.is_acyclic(), "seed {}", seed);
+    }
+}
+
+#[test]
+fn large_min_room_size_leaves_one_open_room() {
+    let settings = DivisionSettings { min_room_size: usize::MAX, ..DivisionSettings::default() };
+    let mut maze = Maze::empty(15, 10);
+    let steps = run(&mut RecursiveDivision::with_settings(None, settings, 3), &mut maze);
+
+    assert_eq!(steps, 0);
+    assert_eq!(count_interior_east_walls(&maze), 0);
+    assert_eq!(count_interior_south_walls(&maze), 0);
+    assert!(maze.validate().is_symmetric());
+}
+
+#[test]
+fn min_room_size_stops_division_at_room_size() {
+    let settings = DivisionSettings { min_room_size: 4, ..DivisionSettings::default() };
+    let mut maze = Maze::empty(4, 4);
+    run(&mut RecursiveDivision::with_settings(None, settings, 3), &mut maze);
+    assert_eq!(count_interior_east_walls(&maze) + count_interior_south_walls(&maze), 0);
+
+    let mut maze = Maze::empty(5, 4);
+    run(&mut RecursiveDivision::with_settings(None, settings, 3), &mut maze);
+    assert!(count_interior_east_walls(&maze) > 0);
+}
+
+#[test]
+fn doorways_are_clamped_to_the_wall_length() {
+    //  A maze wider than it is tall is first divided by a vertical wall spanning its full height of five cells
+    for (doorways, gaps) in [(0, 1), (1, 1), (3, 3), (5, 5), (100, 5)] {
+        for seed in 0..10 {
+            let settings = DivisionSettings { doorways, ..DivisionSettings::default() };
+            let mut maze = Maze::empty(12, 5);
+            run(&mut RecursiveDivision::with_settings(Some(1), settings, seed), &mut maze);
+
+            assert_eq!(count_interior_east_walls(&maze), 5 - gaps, "doorways {} seed {}", doorways, seed);
+            assert_eq!(count_interior_south_walls(&maze), 0, "doorways {} seed {}", doorways, seed);
+        }
+    }
+}
+
+#[test]
+fn room_chance_never_leaves_the_whole_maze_undivided() {
+    let settings = DivisionSettings { room_chance: 1.0, ..DivisionSettings::default() };
+
+    for seed in 0..10 {
+        let mut maze = Maze::empty(12, 5);
+        let steps = run(&mut RecursiveDivision::with_settings(None, settings, seed), &mut maze);
+
+        //  The whole maze is divided once, after which both halves are left as rooms
+        assert_eq!(steps, 1, "seed {}", seed);
+        assert_eq!(count_interior_east_walls(&maze), 4, "seed {}", seed);
+        assert_eq!(count_interior_south_walls(&maze), 0, "seed {}", seed);
+        let report = maze.validate();
+        assert!(report.is_symmetric() && report.is_connected(), "seed {}: {}", seed, report);
+    }
+}
+
+#[test]
+fn finishes_once_no_field_can_be_divided() {
+    //  Every step divides all queued fields, and random splits halve them on average, so generation takes
+    //  a logarithmic number of steps rather than running through a fixed number of empty iterations
+    for (width, height, seeds) in [(1, 1, 10), (40, 1, 10), (2, 2, 10), (10, 10, 10), (100, 60, 10), (500, 500, 3)] {
+        for seed in 0..seeds {
+            let mut maze = Maze::empty(width, height);
+            let steps = run(&mut RecursiveDivision::new(None, seed), &mut maze);
+
+            let max_steps = 4.0 * ((width * height) as f64).log2() + 2.0;
+            assert!(steps as f64 <= max_steps, "{}x{} seed {}: {} steps", width, height, seed, steps);
+            assert!(maze.validate().is_perfect(), "{}x{} seed {}", width, height, seed);
+        }
+    }
+}
+
+#[test]
+fn max_subdivides_limits_the_number_of_steps() {
+    for seed in 0..10 {
+        let mut maze = Maze::empty(100, 60);
+        let unlimited_steps = run(&mut RecursiveDivision::new(None, seed), &mut maze);
+
+        for max_subdivides in [1, 5, unlimited_steps - 1] {
+            let mut maze = Maze::empty(100, 60);
+            let steps = run(&mut RecursiveDivision::new(Some(max_subdivides), seed), &mut maze);
+            assert_eq!(steps, max_subdivides, "seed {}", seed);
+        }
+
+        //  A limit that is never reached does not keep the generator going
+        let mut maze = Maze::empty(100, 60);
+        let steps = run(&mut RecursiveDivision::new(Some(unlimited_steps + 10), seed), &mut maze);
+        assert_eq!(steps, unlimited_steps, "seed {}", seed);
+    }
+
+    let mut maze = Maze::empty(1, 1);
+    assert_eq!(run(&mut RecursiveDivision::new(Some(5), 3), &mut maze), 0);
+}
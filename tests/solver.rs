@@ -0,0 +1,297 @@
+mod common;
+
+use std::collections::VecDeque;
+
+use common::{generate, open_east};
+use mazegen::{
+    braid, get_open_neighbors, longest_path_endpoints, place_start_and_goal, solve, AStar, BreadthFirst,
+    DeadEndFilling, DistanceMap, Hand, Heuristic, Maze, MazeGenerator, MazeSolver, OriginShift, Tremaux, WalkOutcome,
+    WallFollower,
+};
+
+/// Generates a maze with loops, by braiding away every dead end.
+fn generate_braided(width: usize, height: usize, seed: u64) -> Maze {
+    let mut maze = generate(width, height, seed);
+    braid(&mut maze, 1.0, seed);
+    maze
+}
+
+/// Returns the number of passages on a shortest path from *start* to *goal*, found independently of the solvers.
+fn shortest_distance(maze: &Maze, start: usize, goal: usize) -> Option<usize> {
+    let mut distances = vec![None; maze.cells.len()];
+    let mut queue = VecDeque::from([start]);
+    distances[start] = Some(0);
+
+    while let Some(cell_idx) = queue.pop_front() {
+        for nbor_idx in get_open_neighbors(cell_idx, maze) {
+            if distances[nbor_idx].is_none() {
+                distances[nbor_idx] = distances[cell_idx].map(|distance| distance + 1);
+                queue.push_back(nbor_idx);
+            }
+        }
+    }
+    distances[goal]
+}
+
+/// Asserts that *path* leads from *start* to *goal* through open passages only.
+fn assert_valid_path(maze: &Maze, path: &[usize], start: usize, goal: usize) {
+    assert_eq!(path.first(), Some(&start));
+    assert_eq!(path.last(), Some(&goal));
+    for pair in path.windows(2) {
+        assert!(get_open_neighbors(pair[0], maze).contains(&pair[1]), "no passage from {} to {}", pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn breadth_first_finds_shortest_path() {
+    for seed in 0..20 {
+        let maze = generate_braided(24, 16, seed);
+        let goal = maze.cells.len() - 1;
+
+        let path = solve(&mut BreadthFirst::new(), &maze, 0, goal).unwrap();
+        assert_valid_path(&maze, &path, 0, goal);
+        assert_eq!(Some(path.len() - 1), shortest_distance(&maze, 0, goal), "seed {}", seed);
+    }
+}
+
+#[test]
+fn breadth_first_handles_trivial_and_unreachable_goals() {
+    let maze = generate(8, 8, 1);
+    assert_eq!(solve(&mut BreadthFirst::new(), &maze, 5, 5), Some(vec![5]));
+
+    let walled = Maze::empty(8, 8);
+    let mut bfs = BreadthFirst::new();
+    assert_eq!(solve(&mut bfs, &walled, 0, 63), None);
+    assert_eq!(bfs.outcome(), WalkOutcome::GaveUp);
+
+    bfs.initialize(&maze, 0, 63);
+    assert_eq!(bfs.outcome(), WalkOutcome::Walking);
+    assert!(solve(&mut bfs, &maze, 0, 63).is_some());
+    assert_eq!(bfs.outcome(), WalkOutcome::ReachedGoal);
+}
+
+#[test]
+fn a_star_matches_breadth_first_for_every_heuristic() {
+    let mut bfs_expanded = 0;
+    let mut manhattan_expanded = 0;
+
+    for seed in 0..20 {
+        let maze = generate_braided(24, 16, seed);
+        let goal = maze.cells.len() - 1;
+
+        let mut bfs = BreadthFirst::new();
+        let bfs_path = solve(&mut bfs, &maze, 0, goal).unwrap();
+        bfs_expanded += bfs.nodes_expanded().unwrap();
+
+        for heuristic in [Heuristic::Manhattan, Heuristic::Euclidean, Heuristic::Zero] {
+            let mut a_star = AStar::new(heuristic);
+            let path = solve(&mut a_star, &maze, 0, goal).unwrap();
+            assert_valid_path(&maze, &path, 0, goal);
+            assert_eq!(path.len(), bfs_path.len(), "seed {} {:?}", seed, heuristic);
+
+            if heuristic == Heuristic::Manhattan {
+                manhattan_expanded += a_star.nodes_expanded().unwrap();
+            }
+        }
+    }
+
+    //  Guided by the heuristic, A* gets away with expanding fewer cells
+    assert!(manhattan_expanded < bfs_expanded, "{} vs {}", manhattan_expanded, bfs_expanded);
+}
+
+#[test]
+fn wall_follower_solves_perfect_mazes() {
+    for seed in 0..20 {
+        let maze = generate(24, 16, seed);
+        let goal = maze.cells.len() / 2 + 12;
+
+        for hand in [Hand::Left, Hand::Right] {
+            let mut follower = WallFollower::new(hand);
+            let path = solve(&mut follower, &maze, 0, goal).unwrap();
+            assert_eq!(follower.outcome(), WalkOutcome::ReachedGoal);
+            assert_valid_path(&maze, &path, 0, goal);
+        }
+    }
+}
+
+#[test]
+fn wall_follower_loops_in_braided_mazes() {
+    let mut looped = 0;
+    for seed in 0..20 {
+        //  The goal sits in the middle of the maze, away from the outer wall the walker starts out along
+        let maze = generate_braided(24, 16, seed);
+        let goal = maze.cells.len() / 2 + 12;
+
+        let mut follower = WallFollower::new(Hand::Left);
+        let path = solve(&mut follower, &maze, 0, goal);
+        match follower.outcome() {
+            WalkOutcome::ReachedGoal => assert_valid_path(&maze, &path.unwrap(), 0, goal),
+            WalkOutcome::Looped => {
+                assert!(path.is_none());
+                looped += 1;
+            }
+            outcome => panic!("seed {}: {:?}", seed, outcome),
+        }
+    }
+
+    assert!(looped > 0);
+}
+
+#[test]
+fn tremaux_always_reaches_the_goal() {
+    for seed in 0..20 {
+        for maze in [generate(24, 16, seed), generate_braided(24, 16, seed)] {
+            let goal = maze.cells.len() / 2 + 12;
+
+            let mut tremaux = Tremaux::new(seed);
+            let path = solve(&mut tremaux, &maze, 0, goal).unwrap();
+            assert_eq!(tremaux.outcome(), WalkOutcome::ReachedGoal);
+            assert_valid_path(&maze, &path, 0, goal);
+            //  Every passage is walked at most twice, and the trail starts out with the start cell
+            let passages: usize = (0..maze.cells.len()).map(|idx| get_open_neighbors(idx, &maze).len()).sum::<usize>() / 2;
+            assert!(tremaux.trail().len() <= 2 * passages + 1);
+        }
+    }
+}
+
+#[test]
+fn tremaux_walks_a_living_maze() {
+    let mut reached = 0;
+    for seed in 0..10 {
+        //  Step the walls and the walker in turn, like the visualizer does, restarting the walker when it gives up
+        let mut maze = Maze::empty(10, 10);
+        let mut generator = OriginShift::new(1, seed);
+        generator.initialize(&mut maze);
+        let (start, goal) = place_start_and_goal(&mut maze, true).unwrap();
+
+        let mut tremaux = Tremaux::new(seed);
+        tremaux.initialize(&maze, start, goal);
+        for _ in 0..5000 {
+            generator.step(&mut maze);
+            tremaux.step(&maze);
+
+            match tremaux.outcome() {
+                WalkOutcome::ReachedGoal => {
+                    reached += 1;
+                    tremaux.initialize(&maze, start, goal);
+                }
+                WalkOutcome::GaveUp => tremaux.initialize(&maze, start, goal),
+                _ => {}
+            }
+        }
+    }
+
+    assert!(reached > 0);
+}
+
+#[test]
+fn tremaux_gives_up_on_unreachable_goal() {
+    //  Wall in the bottom right corner
+    let mut maze = generate_braided(8, 8, 1);
+    let goal = maze.cells.len() - 1;
+    maze.cells[goal].wall_north = true;
+    maze.cells[goal].wall_west = true;
+    maze.cells[goal - 8].wall_south = true;
+    maze.cells[goal - 1].wall_east = true;
+
+    let mut tremaux = Tremaux::new(1);
+    assert_eq!(solve(&mut tremaux, &maze, 0, goal), None);
+    assert_eq!(tremaux.outcome(), WalkOutcome::GaveUp);
+}
+
+#[test]
+fn dead_end_filling_leaves_the_solution_of_perfect_mazes() {
+    for seed in 0..20 {
+        let maze = generate(24, 16, seed);
+        let goal = maze.cells.len() / 2 + 12;
+        let bfs_path = solve(&mut BreadthFirst::new(), &maze, 0, goal).unwrap();
+
+        let mut filling = DeadEndFilling::new();
+        let path = solve(&mut filling, &maze, 0, goal).unwrap();
+        assert_eq!(path, bfs_path, "seed {}", seed);
+
+        let mut solution_cells = bfs_path.clone();
+        solution_cells.sort_unstable();
+        assert_eq!(filling.solution_cells(), solution_cells, "seed {}", seed);
+    }
+}
+
+#[test]
+fn dead_end_filling_finds_shortest_path_through_loops() {
+    for seed in 0..20 {
+        let maze = generate_braided(24, 16, seed);
+        let goal = maze.cells.len() - 1;
+
+        let path = solve(&mut DeadEndFilling::new(), &maze, 0, goal).unwrap();
+        assert_valid_path(&maze, &path, 0, goal);
+        assert_eq!(Some(path.len() - 1), shortest_distance(&maze, 0, goal), "seed {}", seed);
+    }
+}
+
+#[test]
+fn distance_map_matches_shortest_paths() {
+    for seed in 0..10 {
+        let maze = generate_braided(24, 16, seed);
+        let from = maze.cells.len() / 2 + 12;
+        let distances = DistanceMap::new(&maze, from);
+
+        assert_eq!(distances.from(), from);
+        assert_eq!(distances.distance(from), Some(0));
+        for cell_idx in 0..maze.cells.len() {
+            assert_eq!(distances.distance(cell_idx), shortest_distance(&maze, from, cell_idx), "seed {}", seed);
+        }
+
+        let max_distance = distances.distances().iter().flatten().copied().max().unwrap();
+        assert_eq!(distances.max_distance(), max_distance);
+        assert_eq!(distances.distance(distances.farthest()), Some(max_distance));
+    }
+}
+
+#[test]
+fn distance_map_skips_unreachable_cells() {
+    let distances = DistanceMap::new(&Maze::empty(4, 4), 5);
+
+    assert_eq!(distances.farthest(), 5);
+    assert_eq!(distances.max_distance(), 0);
+    assert!(distances.distances().iter().enumerate().all(|(idx, distance)| distance.is_some() == (idx == 5)));
+}
+
+#[test]
+fn endpoints_span_the_longest_path_of_perfect_mazes() {
+    for seed in 0..5 {
+        let maze = generate(12, 10, seed);
+        let (start, goal) = longest_path_endpoints(&maze, false);
+
+        let longest = (0..maze.cells.len()).map(|from| DistanceMap::new(&maze, from).max_distance()).max();
+        assert_eq!(DistanceMap::new(&maze, start).distance(goal), longest, "seed {}", seed);
+    }
+}
+
+#[test]
+fn endpoints_skip_isolated_cells() {
+    //  The top row is walled off cell by cell, only the bottom row is a corridor
+    let mut maze = Maze::empty(5, 2);
+    for west_idx in 5..9 {
+        open_east(&mut maze, west_idx);
+    }
+
+    assert_eq!(longest_path_endpoints(&maze, true), (9, 5));
+    assert_eq!(place_start_and_goal(&mut maze, true), Some((9, 5)));
+    assert!(!maze.cells[5].wall_west);
+    assert!(!maze.cells[9].wall_south);
+}
+
+#[test]
+fn endpoints_are_not_placed_without_a_path() {
+    let mut maze = Maze::empty(5, 3);
+    assert_eq!(place_start_and_goal(&mut maze, true), None);
+    assert_eq!(maze.cells, Maze::empty(5, 3).cells);
+
+    //  The only corridor runs through the middle, touching the border at a single cell
+    let mut maze = Maze::empty(5, 3);
+    for west_idx in 5..8 {
+        open_east(&mut maze, west_idx);
+    }
+    assert_eq!(place_start_and_goal(&mut maze, true), None);
+    assert_eq!(place_start_and_goal(&mut maze, false), Some((8, 5)));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{run, run_with};
+use mazegen::{
+    braid, AldousBroder, BinaryTree, CellSelection, CellularAutomaton, Dir, Eller, GrowingTree, HuntAndKill, Kruskal,
+    LifeRule, Maze, MazeGenerator, MazeViolation, OriginShift, Prim, RecursiveBacktracker, RecursiveDivision,
+    Sidewinder, Wilson,
+};
+
+/// Checks that the walls are symmetric, meant to be called after every step of a generator.
+fn assert_symmetric(maze: &Maze) {
+    let report = maze.validate();
+    assert!(report.is_symmetric(), "{}", report);
+}
+
+#[test]
+fn perfect_generators_produce_perfect_mazes() {
+    let generators: Vec<(&str, Box<dyn MazeGenerator>)> = vec![
+        ("recursive backtracker", Box::new(RecursiveBacktracker::new(3))),
+        ("recursive division", Box::new(RecursiveDivision::new(None, 3))),
+        ("binary tree", Box::new(BinaryTree::new(Dir::North, Dir::East, 3).unwrap())),
+        ("prim", Box::new(Prim::new(3))),
+        ("kruskal", Box::new(Kruskal::new(3))),
+        ("wilson", Box::new(Wilson::new(3))),
+        ("aldous broder", Box::new(AldousBroder::new(3))),
+        ("eller", Box::new(Eller::new(3))),
+        ("sidewinder", Box::new(Sidewinder::new(Dir::North, 3))),
+        ("hunt and kill", Box::new(HuntAndKill::new(3))),
+        ("growing tree", Box::new(GrowingTree::new(CellSelection::Middle, 3))),
+    ];
+
+    for (name, mut generator) in generators {
+        let mut maze = Maze::empty(23, 17);
+        run_with(generator.as_mut(), &mut maze, assert_symmetric);
+
+        let report = maze.validate();
+        assert!(report.is_perfect(), "{}: {}", name, report);
+    }
+}
+
+#[test]
+fn origin_shift_stays_perfect() {
+    let mut maze = Maze::empty(16, 9);
+    let mut generator = OriginShift::new(1, 3);
+    generator.initialize(&mut maze);
+
+    for _ in 0..500 {
+        generator.step(&mut maze);
+        let report = maze.validate();
+        assert!(report.is_perfect(), "{}", report);
+    }
+}
+
+#[test]
+fn reports_loops_and_unreachable_cells() {
+    let mut maze = Maze::empty(20, 20);
+    run(&mut RecursiveBacktracker::new(3), &mut maze);
+    assert!(braid(&mut maze, 1.0, 3) > 0);
+
+    let report = maze.validate();
+    assert!(report.is_symmetric() && report.is_connected());
+    assert!(!report.is_acyclic());
+
+    let mut maze = Maze::empty(30, 12);
+    run_with(&mut CellularAutomaton::new(LifeRule::CAVE, 0.45, 50, 3), &mut maze, assert_symmetric);
+
+    let report = Maze::empty(2, 2).validate();
+    assert!(report.is_acyclic());
+    assert_eq!(
+        report.violations,
+        vec![
+            MazeViolation::Unreachable { x: 1, y: 0 },
+            MazeViolation::Unreachable { x: 0, y: 1 },
+            MazeViolation::Unreachable { x: 1, y: 1 },
+        ]
+    );
+}
+
+#[test]
+fn reports_asymmetric_walls() {
+    let mut maze = Maze::empty(3, 3);
+    run(&mut RecursiveBacktracker::new(3), &mut maze);
+
+    //  Knock down the wall on one side only, between (1, 1) and (2, 1)
+    maze.cells[4].wall_east = !maze.cells[4].wall_east;
+
+    let report = maze.validate();
+    assert!(!report.is_symmetric());
+    assert!(report.violations.contains(&MazeViolation::AsymmetricWall { x: 1, y: 1, dir: Dir::East }));
+}
+
+#[test]
+fn reports_violations_grouped_by_kind() {
+    //  Open up the left three columns completely, which closes a loop, and leave the right column walled off
+    let mut maze = Maze::empty(4, 2);
+    for idx in [0, 1, 4, 5] {
+        maze.cells[idx].wall_east = false;
+        maze.cells[idx + 1].wall_west = false;
+    }
+    for idx in [0, 1, 2] {
+        maze.cells[idx].wall_south = false;
+        maze.cells[idx + 4].wall_north = false;
+    }
+
+    //  The asymmetric wall is found after the loop, but reported first
+    maze.cells[5].wall_east = true;
+
+    assert_eq!(
+        maze.validate().violations,
+        vec![
+            MazeViolation::AsymmetricWall { x: 1, y: 1, dir: Dir::East },
+            MazeViolation::Loop { x: 0, y: 1, dir: Dir::East },
+            MazeViolation::Unreachable { x: 3, y: 0 },
+            MazeViolation::Unreachable { x: 3, y: 1 },
+        ]
+    );
+}
@@ -0,0 +1,15 @@
+use mazegen::viz::{Framebuffer, MazeVizDescritptor};
+use mazegen::Maze;
+
+#[test]
+fn maze_filling_the_framebuffer_stays_inside_it() {
+    let mut framebuffer = Framebuffer { buffer: vec![0; 100 * 100], width: 100, height: 100 };
+    let desc = MazeVizDescritptor::new(0, 0, 100, 100, 10, 10);
+
+    framebuffer.draw_maze(&Maze::empty(10, 10), &desc);
+    framebuffer.draw_path(&Maze::empty(10, 10), &[98, 99], 0x1, &desc);
+
+    //  The outer walls along the far edges are clipped rather than drawn past the framebuffer
+    assert_ne!(framebuffer.buffer[0], 0);
+    assert_ne!(framebuffer.buffer[99 * 100 + 99], 0);
+}